@@ -0,0 +1,55 @@
+//! Times `UnionFind` on generated inputs of millions of elements: clustered
+//! like day 8, where random connections are merged until everything is
+//! connected, and merged into the deepest trees union by size allows.
+//! Run with `cargo bench --bench union_find`.
+
+use aoc2025::UnionFind;
+use aoc2025::bench::{BenchConfig, bench};
+use aoc2025::runner::format_table;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{merge_pairwise, random_edges};
+
+/// Merges the edges in order until a single component is left, returning how
+/// many edges that took.
+fn cluster(n: usize, edges: &[(usize, usize)]) -> usize {
+    let mut uf = UnionFind::new(n);
+    for (i, &(x, y)) in edges.iter().enumerate() {
+        if uf.union(x, y) && uf.component_count() == 1 {
+            return i + 1;
+        }
+    }
+    edges.len()
+}
+
+/// Finds the root of every element of the deepest trees union by size
+/// builds, about log2 n levels.
+fn deepest(n: usize) -> usize {
+    let mut uf = merge_pairwise(n);
+    (0..n).map(|i| uf.find(i)).filter(|&root| root == 0).count()
+}
+
+fn main() {
+    let config = BenchConfig { samples: Some(10) };
+    let header = ["Input", "Elements", "Min", "Median", "p95"].map(String::from);
+    let mut rows = vec![];
+    for n in [1_000_000, 4_000_000] {
+        // Random graphs connect after about n ln n / 2 edges.
+        let edges = random_edges(n, 10 * n);
+        for (name, stats) in [
+            ("clustering", bench(config, || cluster(n, &edges))),
+            ("deepest", bench(config, || deepest(n))),
+        ] {
+            rows.push([
+                name.to_string(),
+                n.to_string(),
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+            ]);
+        }
+    }
+    print!("{}", format_table(&header, &rows));
+}
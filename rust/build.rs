@@ -0,0 +1,174 @@
+//! Generates the day registry from the `src/days/dayNN.rs` files, so that a
+//! day is registered exactly when its module exists. Problems with a module
+//! are reported as `compile_error!`s pointing at the offending file. Also
+//! hashes the code every day builds on, for the answer cache to key on.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Module {
+    name: String,
+    solution: String,
+    path: PathBuf,
+}
+
+/// The day a file stem such as `day07` stands for. `day00` is the template
+/// and is not a day.
+fn day_of(stem: &str) -> Option<u8> {
+    let digits = stem.strip_prefix("day")?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|&day| day > 0)
+}
+
+/// The type named in `impl Solution for <type>`.
+fn solution_name(source: &str) -> Option<String> {
+    let rest = &source[source.find("impl Solution for ")? + "impl Solution for ".len()..];
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    Some(name).filter(|name| !name.is_empty())
+}
+
+/// The files outside `src/days` that solutions depend on, relative to the crate
+/// root.
+const SHARED_SOURCES: &[&str] = &["src/answer.rs", "src/parse.rs", "src/days.rs", "Cargo.lock"];
+
+/// The same 64-bit FNV-1a as `src/hash.rs`, continuing from `hash`, since the
+/// build script cannot use the crate it builds.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Every `.rs` file under `dir`, recursively.
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for entry in
+        fs::read_dir(dir).unwrap_or_else(|e| panic!("Could not read {}: {}", dir.display(), e))
+    {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(rust_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Hashes the path and contents of every shared source, so that renaming,
+/// adding or editing one changes the hash. Missing files, such as a
+/// `Cargo.lock` that has not been generated yet, hash as absent.
+fn shared_source_hash(root: &Path) -> u64 {
+    let utils = root.join("src/utils");
+    println!("cargo:rerun-if-changed={}", utils.display());
+    let mut paths = rust_files(&utils);
+    paths.extend(SHARED_SOURCES.iter().map(|path| root.join(path)));
+    paths.sort();
+
+    let mut hash = 0xcbf29ce484222325;
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let relative = path.strip_prefix(root).unwrap();
+        hash = fnv1a(hash, relative.to_string_lossy().as_bytes());
+        if let Ok(contents) = fs::read(&path) {
+            hash = fnv1a(hash, &[0]);
+            hash = fnv1a(hash, &contents);
+        }
+    }
+    hash
+}
+
+fn check(path: &Path, source: &str) -> Result<String, String> {
+    let solution = solution_name(source)
+        .ok_or_else(|| format!("{} does not implement Solution", path.display()))?;
+    let missing: Vec<&str> = ["part1", "part2"]
+        .into_iter()
+        .filter(|part| !source.contains(&format!("fn {}(", part)))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} does not define {}",
+            path.display(),
+            missing.join(" or ")
+        ));
+    }
+    Ok(solution)
+}
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let days_dir = root.join("src/days");
+    println!("cargo:rerun-if-changed={}", days_dir.display());
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&days_dir)
+        .unwrap_or_else(|e| panic!("Could not read {}: {}", days_dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    paths.sort();
+
+    let mut modules: BTreeMap<u8, Module> = BTreeMap::new();
+    let mut errors = vec![];
+    for path in paths {
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let Some(day) = day_of(&name) else {
+            continue;
+        };
+        let relative = path.strip_prefix(&root).unwrap();
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Could not read {}: {}", path.display(), e));
+
+        match check(relative, &source) {
+            Ok(solution) => {
+                if let Some(other) = modules.get(&day) {
+                    errors.push(format!(
+                        "day {} is claimed by both src/days/{}.rs and src/days/{}.rs",
+                        day, other.name, name
+                    ));
+                    continue;
+                }
+                modules.insert(
+                    day,
+                    Module {
+                        name,
+                        solution,
+                        path,
+                    },
+                );
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    let mut out = String::new();
+    for e in errors {
+        out += &format!("compile_error!({:?});\n", e);
+    }
+    out += "days!(\n";
+    for (day, module) in &modules {
+        out += &format!(
+            "    {} => {}::{} in {:?},\n",
+            day,
+            module.name,
+            module.solution,
+            module.path.display().to_string()
+        );
+    }
+    out += ");\n";
+    out += &format!(
+        "\n/// Hash of the code outside `src/days` that solutions build on.\n\
+         pub const SHARED_SOURCE_HASH: u64 = {:#018x};\n",
+        shared_source_hash(&root)
+    );
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(&out_path, out)
+        .unwrap_or_else(|e| panic!("Could not write {}: {}", out_path.display(), e));
+}
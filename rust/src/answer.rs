@@ -0,0 +1,122 @@
+use crate::parse::ParseError;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    /// An integer that does not fit in an `i64`.
+    BigInt(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => n.into(),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    (n as i128).into()
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Why a day could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input is malformed. Boxed to keep results small.
+    Parse(Box<ParseError>),
+    /// The input is well-formed but has no answer.
+    Unsolvable(String),
+    /// Returned by the parts of the day00 template until they are filled in.
+    NotImplemented,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(e) => write!(f, "parse error: {}", e),
+            SolveError::Unsolvable(msg) => write!(f, "unsolvable: {}", msg),
+            SolveError::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        SolveError::Parse(Box::new(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(Answer::from(3u32), Answer::Int(3));
+        assert_eq!(Answer::from(-3i64), Answer::Int(-3));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(Answer::from(5i128), Answer::Int(5));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Int(-7).to_string(), "-7");
+        assert_eq!(
+            Answer::BigInt(1 << 80).to_string(),
+            (1u128 << 80).to_string()
+        );
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        let line = "x";
+        assert_eq!(
+            SolveError::from(ParseError::new(0, line, line, "a number")).to_string(),
+            "parse error: line 1, column 1: expected a number, found 'x'"
+        );
+        assert_eq!(SolveError::NotImplemented.to_string(), "not implemented");
+    }
+}
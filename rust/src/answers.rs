@@ -0,0 +1,341 @@
+use crate::input::profile_dir;
+use crate::runner::{DayOutcome, DayReport};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Known-good answers for the real inputs of a profile, stored next to them as
+///
+/// ```toml
+/// [day01]
+/// part1 = "1165"
+/// part2 = "6496"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+pub fn answers_path(profile: &str) -> PathBuf {
+    profile_dir(profile).join("answers.toml")
+}
+
+impl Answers {
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    /// Loads the answers file, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Could not read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string())
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    }
+
+    /// Parses the small TOML subset written by `Display`: `[dayNN]` tables
+    /// holding `partN = "answer"` keys, plus comments and blank lines. The
+    /// answers may contain the escapes `\"`, `\\` and `\n`.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+        let mut day = None;
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            let err = |msg: &str| format!("line {}: {}", i + 1, msg);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let n = table
+                    .strip_prefix("day")
+                    .and_then(|n| n.parse::<u8>().ok())
+                    .ok_or_else(|| err("expected a table like [day01]"))?;
+                day = Some(n);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("expected `partN = \"answer\"`"))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(err("expected key part1 or part2")),
+            };
+            let value = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .and_then(unescape)
+                .ok_or_else(|| err("expected a quoted answer"))?;
+            let day = day.ok_or_else(|| err("answer outside of a [dayNN] table"))?;
+
+            answers.insert(day, part, value);
+        }
+
+        Ok(answers)
+    }
+}
+
+/// Escapes an answer for a TOML basic string, so that text answers with
+/// quotes, backslashes or line breaks read back unchanged.
+fn escape(answer: &str) -> String {
+    let mut escaped = String::with_capacity(answer.len());
+    for c in answer.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Undoes `escape`, rejecting other escapes and unescaped quotes.
+fn unescape(value: &str) -> Option<String> {
+    let mut answer = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return None,
+            '\\' => answer.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                _ => return None,
+            }),
+            c => answer.push(c),
+        }
+    }
+    Some(answer)
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut last_day = None;
+        for (&(day, part), answer) in &self.answers {
+            if last_day != Some(day) {
+                if last_day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{:02}]", day)?;
+                last_day = Some(day);
+            }
+            writeln!(f, "part{} = \"{}\"", part, escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    Missing,
+    /// The part failed, panicked or timed out, so there is no answer to
+    /// compare.
+    Error {
+        error: String,
+    },
+}
+
+impl CheckStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, CheckStatus::Fail { .. } | CheckStatus::Error { .. })
+    }
+}
+
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    /// The answer as shown in the results table.
+    pub actual: String,
+    pub status: CheckStatus,
+}
+
+/// Compares every solved part against the stored answers. Days that are not
+/// implemented have nothing to check and are left out.
+pub fn check(answers: &Answers, reports: &[DayReport]) -> Vec<Check> {
+    let mut checks = vec![];
+    for report in reports {
+        let DayOutcome::Solved { part1, part2, .. } = &report.outcome else {
+            continue;
+        };
+        for (part, result) in [(1, part1), (2, part2)] {
+            if result.answer.is_err() && !result.failed() {
+                continue;
+            }
+            let status = match (&result.answer, answers.get(report.day, part)) {
+                (Err(e), _) => CheckStatus::Error {
+                    error: e.to_string(),
+                },
+                (Ok(actual), Some(expected)) if expected == actual.to_string() => CheckStatus::Pass,
+                (Ok(_), Some(expected)) => CheckStatus::Fail {
+                    expected: expected.to_string(),
+                },
+                (Ok(_), None) => CheckStatus::Missing,
+            };
+            checks.push(Check {
+                day: report.day,
+                part,
+                actual: result.display_answer(),
+                status,
+            });
+        }
+    }
+    checks
+}
+
+/// Stores the answers of every `Missing` check, returning how many were added.
+pub fn record(answers: &mut Answers, checks: &[Check]) -> usize {
+    let mut recorded = 0;
+    for c in checks {
+        if c.status == CheckStatus::Missing {
+            answers.insert(c.day, c.part, c.actual.clone());
+            recorded += 1;
+        }
+    }
+    recorded
+}
+
+pub fn render_checks(checks: &[Check]) -> String {
+    let mut out = String::new();
+    for c in checks {
+        let status = match &c.status {
+            CheckStatus::Pass => "PASS".to_string(),
+            CheckStatus::Fail { expected } => {
+                format!("FAIL (expected {}, got {})", expected, c.actual)
+            }
+            CheckStatus::Missing => format!("MISSING (got {})", c.actual),
+            CheckStatus::Error { error } => format!("ERROR ({})", error),
+        };
+        out.push_str(&format!("Day {:>2} part {}: {}\n", c.day, c.part, status));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::{Answer, SolveError};
+    use crate::runner::{PartError, PartResult};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let content = r#"# answers for the real inputs
+[day01]
+part1 = "1165"
+part2 = "6496"
+
+[day09]
+part1 = "4758598740"
+"#;
+        let answers = Answers::parse(content).unwrap();
+        assert_eq!(answers.get(1, 2), Some("6496"));
+        assert_eq!(answers.get(9, 2), None);
+        assert_eq!(Answers::parse(&answers.to_string()), Ok(answers));
+
+        assert!(Answers::parse("part1 = \"1\"").is_err());
+        assert!(Answers::parse("[day01]\npart3 = \"1\"").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1").is_err());
+
+        let mut answers = Answers::default();
+        answers.insert(2, 1, r#"say "hi" \ bye"#.into());
+        answers.insert(2, 2, "two\nlines\\n".into());
+        let content = answers.to_string();
+        assert!(content.contains(r#"part1 = "say \"hi\" \\ bye""#));
+        assert_eq!(Answers::parse(&content), Ok(answers));
+
+        assert!(Answers::parse("[day01]\npart1 = \"a\"b\"").is_err());
+        assert!(Answers::parse("[day01]\npart1 = \"a\\\"").is_err());
+        assert!(Answers::parse("[day01]\npart1 = \"\\t\"").is_err());
+    }
+
+    #[test]
+    fn test_check_and_record() {
+        let mut answers = Answers::default();
+        answers.insert(1, 1, "3".into());
+        let part = |answer| PartResult {
+            answer: Ok(Answer::Int(answer)),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        let panicked = PartResult {
+            answer: Err(PartError::Panic("bad input".into())),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        let reports = vec![
+            DayReport {
+                day: 1,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::ZERO,
+                    part1: part(4),
+                    part2: part(6),
+                },
+                input_hash: Some(0),
+            },
+            DayReport {
+                day: 3,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::ZERO,
+                    part1: panicked,
+                    part2: PartResult {
+                        answer: Err(PartError::Solve(SolveError::NotImplemented)),
+                        elapsed: Duration::ZERO,
+                        cached: false,
+                    },
+                },
+                input_hash: Some(0),
+            },
+            DayReport {
+                day: 2,
+                outcome: DayOutcome::NotImplemented,
+                input_hash: None,
+            },
+        ];
+
+        let checks = check(&answers, &reports);
+        assert_eq!(checks.len(), 3);
+        assert_eq!(
+            checks[0].status,
+            CheckStatus::Fail {
+                expected: "3".into()
+            }
+        );
+        assert_eq!(checks[1].status, CheckStatus::Missing);
+        assert_eq!(
+            checks[2].status,
+            CheckStatus::Error {
+                error: "panicked: bad input".into()
+            }
+        );
+        assert!(render_checks(&checks).ends_with("Day  3 part 1: ERROR (panicked: bad input)\n"));
+        assert!(checks[2].status.is_failure());
+
+        assert_eq!(record(&mut answers, &checks), 1);
+        assert_eq!(answers.get(3, 1), None);
+        assert_eq!(answers.get(3, 2), None);
+        assert_eq!(answers.get(1, 1), Some("3"));
+        assert_eq!(answers.get(1, 2), Some("6"));
+    }
+}
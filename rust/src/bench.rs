@@ -0,0 +1,119 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How long to run a function before measuring, to warm caches and to get a
+/// first estimate of its running time.
+const WARMUP_TIME: Duration = Duration::from_millis(100);
+/// Samples shorter than this are dominated by timer overhead, so fast
+/// functions are run several times per sample.
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(1);
+/// Time budget for the measurement when the sample count is not given.
+const MEASUREMENT_TIME: Duration = Duration::from_secs(1);
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 100;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Fixed number of samples, or `None` to fit the measurement time.
+    pub samples: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// Total number of calls that were measured.
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of per-iteration times.
+    pub fn from_samples(samples: &[Duration], iterations: usize) -> Self {
+        assert!(!samples.is_empty(), "No samples to compute statistics of");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let percentile = |p: f64| {
+            let rank = (p * (sorted.len() - 1) as f64).round() as usize;
+            sorted[rank]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Stats {
+            iterations,
+            min: sorted[0],
+            median: percentile(0.5),
+            mean: Duration::from_secs_f64(mean),
+            p95: percentile(0.95),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Repeatedly times `f`, returning the statistics of a single call.
+///
+/// After a warmup the number of calls per sample is chosen so that each sample
+/// takes at least `MIN_SAMPLE_TIME`, and unless the config fixes it, the number
+/// of samples is chosen to fit `MEASUREMENT_TIME`.
+pub fn bench<R>(config: BenchConfig, mut f: impl FnMut() -> R) -> Stats {
+    let start = Instant::now();
+    let mut warmup_runs = 0;
+    while warmup_runs == 0 || start.elapsed() < WARMUP_TIME {
+        black_box(f());
+        warmup_runs += 1;
+    }
+    let estimate = start.elapsed() / warmup_runs;
+
+    let iters_per_sample = if estimate.is_zero() {
+        MIN_SAMPLE_TIME.as_nanos() as usize
+    } else {
+        (MIN_SAMPLE_TIME.as_nanos() / estimate.as_nanos()).max(1) as usize
+    };
+    let samples = config.samples.unwrap_or_else(|| {
+        let sample_time = estimate.as_secs_f64() * iters_per_sample as f64;
+        ((MEASUREMENT_TIME.as_secs_f64() / sample_time) as usize).clamp(MIN_SAMPLES, MAX_SAMPLES)
+    });
+
+    let times: Vec<Duration> = (0..samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iters_per_sample {
+                black_box(f());
+            }
+            start.elapsed() / iters_per_sample as u32
+        })
+        .collect();
+
+    Stats::from_samples(&times, times.len() * iters_per_sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_micros).collect();
+        let stats = Stats::from_samples(&samples, 20);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(11));
+        assert_eq!(stats.p95, Duration::from_micros(19));
+        assert_eq!(stats.mean, Duration::from_nanos(10_500));
+        assert_eq!(stats.stddev.as_nanos(), 5766);
+    }
+
+    #[test]
+    fn test_bench_fixed_samples() {
+        let mut calls = 0;
+        let stats = bench(BenchConfig { samples: Some(3) }, || calls += 1);
+        assert_eq!(stats.iterations % 3, 0);
+        assert!(calls > stats.iterations);
+    }
+}
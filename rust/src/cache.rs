@@ -0,0 +1,173 @@
+//! Answers of earlier runs, so that days whose input and source have not
+//! changed since need not be solved again.
+
+use crate::answer::Answer;
+use crate::hash::{fnv1a, to_hex};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at a directory to keep cached answers in
+/// instead of `target/aoc-cache`.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// The directory cached answers are kept in. Like the inputs it is resolved
+/// from the crate root, not the working directory.
+pub fn cache_dir() -> PathBuf {
+    match env::var_os(CACHE_DIR_VAR) {
+        Some(dir) => dir.into(),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("target/aoc-cache"),
+    }
+}
+
+/// Everything an answer depends on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: u64,
+    /// The hash of the day's own module.
+    pub source_hash: u64,
+    /// The hash of the code all days share, `days::SHARED_SOURCE_HASH`.
+    pub shared_hash: u64,
+    /// The parameters as rendered by `Params`' `Display`.
+    pub params: String,
+    pub variant: &'static str,
+}
+
+impl Key {
+    fn file_name(&self) -> String {
+        let key = format!(
+            "{}|{}|{}|{}|{}",
+            to_hex(self.input_hash),
+            to_hex(self.source_hash),
+            to_hex(self.shared_hash),
+            self.params,
+            self.variant
+        );
+        format!(
+            "day{:02}-part{}-{}",
+            self.day,
+            self.part,
+            to_hex(fnv1a(key.as_bytes()))
+        )
+    }
+}
+
+/// An answer as stored on disk: its kind on the first line and its value on
+/// the rest, so that text answers round-trip exactly.
+fn encode(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => format!("int\n{}", n),
+        Answer::BigInt(n) => format!("bigint\n{}", n),
+        Answer::Text(s) => format!("text\n{}", s),
+    }
+}
+
+fn decode(s: &str) -> Option<Answer> {
+    let (kind, value) = s.split_once('\n')?;
+    match kind {
+        "int" => value.parse().ok().map(Answer::Int),
+        "bigint" => value.parse().ok().map(Answer::BigInt),
+        "text" => Some(Answer::Text(value.to_string())),
+        _ => None,
+    }
+}
+
+/// A directory of answers with one file per key. The cache is best-effort:
+/// files that cannot be read are misses and answers that cannot be written
+/// are dropped.
+pub struct Cache {
+    dir: PathBuf,
+    read: bool,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache {
+            dir: dir.into(),
+            read: true,
+        }
+    }
+
+    /// The cache in `cache_dir()`.
+    pub fn open() -> Self {
+        Cache::new(cache_dir())
+    }
+
+    /// Ignores the stored answers, but still stores new ones, so that a
+    /// forced re-run refreshes the cache.
+    pub fn refreshing(self) -> Self {
+        Cache {
+            read: false,
+            ..self
+        }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Answer> {
+        if !self.read {
+            return None;
+        }
+        decode(&fs::read_to_string(self.dir.join(key.file_name())).ok()?)
+    }
+
+    pub fn put(&self, key: &Key, answer: &Answer) {
+        if fs::create_dir_all(&self.dir).is_ok() {
+            let _ = fs::write(self.dir.join(key.file_name()), encode(answer));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(part: u8) -> Key {
+        Key {
+            day: 8,
+            part,
+            input_hash: 1,
+            source_hash: 2,
+            shared_hash: 3,
+            params: "connections=1000".into(),
+            variant: "default",
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        for answer in [
+            Answer::Int(-3),
+            Answer::BigInt(1 << 100),
+            Answer::Text("two\nlines".into()),
+        ] {
+            assert_eq!(decode(&encode(&answer)), Some(answer));
+        }
+        assert_eq!(decode("float\n1.5"), None);
+        assert_eq!(decode("int\nx"), None);
+    }
+
+    #[test]
+    fn test_cache() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        assert_eq!(cache.get(&key(1)), None);
+
+        cache.put(&key(1), &Answer::Int(40));
+        assert_eq!(cache.get(&key(1)), Some(Answer::Int(40)));
+        assert_eq!(cache.get(&key(2)), None);
+        let other_params = Key {
+            params: "connections=10".into(),
+            ..key(1)
+        };
+        assert_eq!(cache.get(&other_params), None);
+        let other_shared = Key {
+            shared_hash: 4,
+            ..key(1)
+        };
+        assert_eq!(cache.get(&other_shared), None);
+        assert_eq!(Cache::new(&dir).refreshing().get(&key(1)), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+use crate::days::{self, Params, Tag, get_day};
+use crate::runner::format_table;
+
+/// Renders the metadata and default parameters of every registered day, or
+/// only of the days with `tag`, as a table.
+pub fn render_list(tag: Option<Tag>) -> String {
+    let header = [
+        "Day",
+        "Title",
+        "Part 1",
+        "Part 2",
+        "Examples",
+        "Tags",
+        "Params",
+        "Description",
+    ]
+    .map(String::from);
+
+    let rows: Vec<[String; 8]> = days::DAYS
+        .iter()
+        .filter_map(|&day| Some((day, get_day(day)?)))
+        .filter(|(_, solution)| tag.is_none_or(|tag| solution.info.tags.contains(&tag)))
+        .map(|(day, solution)| {
+            let info = solution.info;
+            let has_examples = solution
+                .examples
+                .iter()
+                .any(|example| example.part1.is_some() || example.part2.is_some());
+            let tags: Vec<&str> = info.tags.iter().map(|tag| tag.name()).collect();
+            [
+                day.to_string(),
+                info.title.to_string(),
+                info.status[0].to_string(),
+                info.status[1].to_string(),
+                if has_examples { "yes" } else { "no" }.to_string(),
+                tags.join(", "),
+                Params::new(solution.params).to_string(),
+                info.description.to_string(),
+            ]
+        })
+        .collect();
+
+    match tag {
+        Some(tag) if rows.is_empty() => format!("No days are tagged {}\n", tag),
+        _ => format_table(&header, &rows),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_list() {
+        let list = render_list(None);
+        assert_eq!(list.lines().count(), 2 + days::DAYS.len());
+        let day1 = list.lines().nth(2).unwrap();
+        assert!(day1.starts_with("1   | Secret Entrance "));
+        assert!(day1.contains("| simulation, math "));
+
+        let list = render_list(Some(Tag::UnionFind));
+        let days: Vec<&str> = list
+            .lines()
+            .skip(2)
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(days, ["8"]);
+    }
+
+    #[test]
+    fn test_tag_from_str() {
+        assert_eq!("union-find".parse(), Ok(Tag::UnionFind));
+        assert_eq!("DP".parse(), Ok(Tag::Dp));
+        assert!("graphs".parse::<Tag>().is_err());
+    }
+}
@@ -0,0 +1,448 @@
+use aoc2025::bench::BenchConfig;
+use aoc2025::days::{Tag, get_day};
+use aoc2025::format::Format;
+use aoc2025::input::{self, DEFAULT_PROFILE, InputSource};
+use aoc2025::runner::parse_days;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: cargo run <days> [options]
+       cargo run new <day>
+       cargo run list [--tag <tag>]
+       cargo run watch <day> [options]
+
+<days> is a single day (9), a range (1-5), a list (3,7,9) or `all`.
+
+Options:
+  --input <path>        read the input from <path>, or from stdin if <path> is `-`
+  --input-str <text>    use <text> as the input
+  --profile <name>      read the inputs and answers of profile <name> from
+                        inputs/<name>/, or run every profile with `all` and
+                        report the parts that fail on any of them
+  --param <name>=<N>    override a parameter of the selected days, such as
+                        connections=10 for day 8; may be repeated
+  --variant <name>      solve the parts that have a variant called <name> with it
+  --cross-check         solve each part with every variant and fail if their
+                        answers differ
+  --example             solve the examples from the puzzle text instead, showing
+                        the expected answers next to the actual ones
+  --check               compare the answers against the answers.toml of the profile
+  --record              like --check, and store answers that are missing
+  --format <format>     print results as text (default), json or csv
+  --no-cache            solve every part again instead of reusing the answers
+                        cached in target/aoc-cache, as --check, --record and
+                        --profile all always do
+  --timeout <duration>  give up on a part after e.g. 30s or 500ms (default: none)
+  --jobs <N>            solve up to N parts at the same time (default: 1)
+  --bench [N]           benchmark each part, taking N samples (default: adaptive);
+                        parts with variants are benchmarked once per variant
+                        and compared
+
+`new <day>` creates src/days/dayNN.rs from the day00 template and an empty
+inputs/dayNN.txt file. The example of the puzzle text goes in the EXAMPLE
+const of the new module, and its answers in EXAMPLES. Every src/days/dayNN.rs
+is registered when it is built.
+
+`watch` runs a day with the given options every time src/days/dayNN.rs or its
+input changes, rebuilding first if needed.
+
+`list` prints the title, status, tags and a description of every day, or only
+of the days tagged <tag> (e.g. grid, geometry, union-find, dp, intervals).
+
+The default profile is inputs/ itself; every directory in it is another
+profile holding dayNN.txt files and an answers.toml of its own. Set
+AOC_INPUT_DIR to read the profiles from another directory.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Options),
+    New(u8),
+    List(Option<Tag>),
+    /// Re-runs the day with the arguments, which select only that day.
+    Watch {
+        day: u8,
+        input: InputSource,
+        args: Vec<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    pub days: Vec<u8>,
+    pub input: InputSource,
+    /// Run every profile, from `--profile all`, instead of only `input`.
+    pub all_profiles: bool,
+    pub params: Vec<(String, usize)>,
+    pub variant: Option<String>,
+    pub cross_check: bool,
+    pub example: bool,
+    pub check: bool,
+    pub record: bool,
+    pub bench: Option<BenchConfig>,
+    pub format: Format,
+    pub no_cache: bool,
+    pub timeout: Option<Duration>,
+    pub jobs: usize,
+}
+
+/// Parses durations like `30s`, `500ms` or a bare number of seconds (`2.5`).
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (number, scale) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, 1e-3)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, 1.0)
+    } else {
+        (s, 1.0)
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n > 0.0)
+        .map(|n| Duration::from_secs_f64(n * scale))
+        .ok_or_else(|| format!("Invalid duration '{}', expected e.g. 30s or 500ms", s))
+}
+
+/// Parses a `--param` value such as `connections=10`.
+fn parse_param(s: &str) -> Result<(String, usize), String> {
+    let invalid = || format!("Invalid parameter '{}', expected e.g. connections=10", s);
+    let (name, value) = s.split_once('=').ok_or_else(invalid)?;
+    let value = value.parse().map_err(|_| invalid())?;
+    if name.is_empty() {
+        return Err(invalid());
+    }
+    Ok((name.to_string(), value))
+}
+
+pub fn parse_command<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    if args.next_if(|arg| arg == "list").is_some() {
+        return parse_list_args(args).map(Command::List);
+    }
+    if args.next_if(|arg| arg == "watch").is_some() {
+        let args: Vec<String> = args.collect();
+        let options = parse_args(args.clone())?;
+        let [day] = options.days[..] else {
+            return Err("`watch` takes a single day".into());
+        };
+        if options.all_profiles {
+            return Err("`watch` takes a single profile".into());
+        }
+        return Ok(Command::Watch {
+            day,
+            input: options.input,
+            args,
+        });
+    }
+    if args.next_if(|arg| arg == "new").is_none() {
+        return parse_args(args).map(Command::Run);
+    }
+
+    let day = args.next().ok_or("Missing day for `new`")?;
+    if let Some(arg) = args.next() {
+        return Err(format!("Unexpected argument '{}'", arg));
+    }
+    day.parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .map(Command::New)
+        .ok_or_else(|| format!("`new` takes a day between 1 and 25, got '{}'", day))
+}
+
+fn parse_list_args(mut args: impl Iterator<Item = String>) -> Result<Option<Tag>, String> {
+    let mut tag = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tag" => tag = Some(args.next().ok_or("Missing value for --tag")?.parse()?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(tag)
+}
+
+pub fn parse_args<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    let mut days = None;
+    let mut input = InputSource::default();
+    let mut profile = None;
+    let mut params = vec![];
+    let mut variant = None;
+    let mut cross_check = false;
+    let mut example = false;
+    let mut check = false;
+    let mut record = false;
+    let mut bench = None;
+    let mut format = Format::Text;
+    let mut no_cache = false;
+    let mut timeout = None;
+    let mut jobs = 1;
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", flag))
+        };
+
+        match arg.as_str() {
+            "--input" => input = InputSource::from_arg(&value("--input")?),
+            "--input-str" => input = InputSource::Inline(value("--input-str")?),
+            "--profile" => profile = Some(value("--profile")?),
+            "--param" => params.push(parse_param(&value("--param")?)?),
+            "--variant" => variant = Some(value("--variant")?),
+            "--cross-check" => cross_check = true,
+            "--format" => format = value("--format")?.parse()?,
+            "--timeout" => timeout = Some(parse_duration(&value("--timeout")?)?),
+            "--jobs" => {
+                jobs = value("--jobs")?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("--jobs must be a positive number")?
+            }
+            "--example" => example = true,
+            "--no-cache" => no_cache = true,
+            "--check" => check = true,
+            "--record" => record = true,
+            "--bench" => {
+                let samples = args.next_if(|a| a.parse::<usize>().is_ok());
+                bench = Some(BenchConfig {
+                    samples: samples.map(|n| n.parse().unwrap()),
+                });
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    let days = days.ok_or("Missing day selection")?;
+    let explicit_input = input.profile().is_none();
+    let all_profiles = profile.as_deref() == Some("all");
+    if explicit_input && profile.is_some() {
+        return Err("--profile cannot be combined with --input or --input-str".into());
+    }
+    if let Some(name) = profile.filter(|_| !all_profiles) {
+        if name != DEFAULT_PROFILE && !input::profile_dir(&name).is_dir() {
+            return Err(format!(
+                "Unknown profile '{}', expected one of: {}, all",
+                name,
+                input::profiles().join(", ")
+            ));
+        }
+        input = InputSource::Profile(name);
+    }
+    if explicit_input && days.len() != 1 {
+        return Err("An explicit input can only be used with a single day".into());
+    }
+    if explicit_input && (check || record) {
+        return Err("--check and --record only work with the inputs of a profile".into());
+    }
+    if all_profiles && (example || bench.is_some() || cross_check || format != Format::Text) {
+        return Err(
+            "--profile all cannot be combined with --example, --bench, --cross-check or \
+             --format"
+                .into(),
+        );
+    }
+    if example && explicit_input {
+        return Err("--example cannot be combined with an explicit input".into());
+    }
+    if example && (check || record || bench.is_some()) {
+        return Err("--example cannot be combined with --check, --record or --bench".into());
+    }
+    if !params.is_empty() && (example || check || record) {
+        return Err(
+            "--param cannot be combined with --example, --check or --record, whose \
+             answers assume the default parameters"
+                .into(),
+        );
+    }
+    for (name, _) in &params {
+        let declared = days
+            .iter()
+            .filter_map(|&day| get_day(day))
+            .any(|solution| solution.declares(name));
+        if !declared {
+            return Err(format!("No selected day has a parameter named '{}'", name));
+        }
+    }
+    if let Some(name) = &variant {
+        let has_variant = days
+            .iter()
+            .filter_map(|&day| get_day(day))
+            .any(|solution| solution.has_variant(name));
+        if !has_variant {
+            return Err(format!("No selected day has a variant named '{}'", name));
+        }
+    }
+    if cross_check && (variant.is_some() || example || check || record || bench.is_some()) {
+        return Err(
+            "--cross-check cannot be combined with --variant, --example, --check, --record \
+             or --bench"
+                .into(),
+        );
+    }
+
+    if bench.is_some() && (check || record) {
+        return Err("--bench cannot be combined with --check or --record".into());
+    }
+    if bench.is_some() && jobs > 1 {
+        return Err("--bench always runs serially and cannot be combined with --jobs".into());
+    }
+    if format != Format::Text && (bench.is_some() || check || example || cross_check) {
+        return Err("--format only applies to plain runs".into());
+    }
+
+    Ok(Options {
+        days,
+        input,
+        all_profiles,
+        params,
+        variant,
+        cross_check,
+        example,
+        check: check || record,
+        record,
+        bench,
+        format,
+        no_cache,
+        timeout,
+        jobs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2.5"), Ok(Duration::from_millis(2500)));
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("1m").is_err());
+    }
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let command = |args: &[&str]| parse_command(args.iter().map(|s| s.to_string()));
+        assert_eq!(command(&["new", "10"]), Ok(Command::New(10)));
+        assert!(matches!(command(&["10"]), Ok(Command::Run(_))));
+        assert!(command(&["new"]).is_err());
+        assert!(command(&["new", "1-3"]).is_err());
+        assert!(command(&["new", "26"]).is_err());
+        assert!(command(&["new", "10", "11"]).is_err());
+        assert_eq!(
+            command(&["watch", "9", "--example"]),
+            Ok(Command::Watch {
+                day: 9,
+                input: InputSource::default(),
+                args: vec!["9".into(), "--example".into()]
+            })
+        );
+        assert!(command(&["watch"]).is_err());
+        assert!(command(&["watch", "1-3"]).is_err());
+        assert!(command(&["watch", "9", "--verbose"]).is_err());
+        assert!(command(&["watch", "9", "--profile", "all"]).is_err());
+        assert_eq!(command(&["list"]), Ok(Command::List(None)));
+        assert_eq!(
+            command(&["list", "--tag", "grid"]),
+            Ok(Command::List(Some(Tag::Grid)))
+        );
+        assert!(command(&["list", "--tag"]).is_err());
+        assert!(command(&["list", "--tag", "graphs"]).is_err());
+        assert!(command(&["list", "9"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse(&["9"]).unwrap();
+        assert_eq!(options.days, vec![9]);
+        assert_eq!(options.input, InputSource::default());
+        assert!(!options.check && !options.all_profiles);
+
+        let options = parse(&["9", "--input", "-"]).unwrap();
+        assert_eq!(options.input, InputSource::Stdin);
+
+        let options = parse(&["--input-str", "1,2", "9"]).unwrap();
+        assert_eq!(options.input, InputSource::Inline("1,2".into()));
+
+        let options = parse(&["1-3", "--example"]).unwrap();
+        assert!(options.example);
+
+        let options = parse(&["all", "--record"]).unwrap();
+        assert!(options.check && options.record);
+
+        let options = parse(&["all", "--format", "csv"]).unwrap();
+        assert_eq!(options.format, Format::Csv);
+
+        let options = parse(&["all", "--timeout", "500ms"]).unwrap();
+        assert_eq!(options.timeout, Some(Duration::from_millis(500)));
+
+        let options = parse(&["all", "--no-cache"]).unwrap();
+        assert!(options.no_cache);
+
+        let options = parse(&["all", "--jobs", "4"]).unwrap();
+        assert_eq!(options.jobs, 4);
+
+        let options =
+            parse(&["all", "--param", "connections=10", "--param", "circuits=2"]).unwrap();
+        assert_eq!(
+            options.params,
+            [("connections".to_string(), 10), ("circuits".to_string(), 2)]
+        );
+
+        let options = parse(&["9", "--variant", "raycast"]).unwrap();
+        assert_eq!(options.variant.as_deref(), Some("raycast"));
+
+        let options = parse(&["all", "--cross-check"]).unwrap();
+        assert!(options.cross_check);
+
+        let options = parse(&["9", "--profile", "default", "--check"]).unwrap();
+        assert_eq!(options.input, InputSource::default());
+
+        let options = parse(&["all", "--profile", "all", "--record"]).unwrap();
+        assert!(options.all_profiles && options.record);
+
+        let options = parse(&["1-3", "--bench"]).unwrap();
+        assert_eq!(options.bench, Some(BenchConfig { samples: None }));
+
+        let options = parse(&["1-3", "--bench", "50"]).unwrap();
+        assert_eq!(options.bench, Some(BenchConfig { samples: Some(50) }));
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["9", "--input"]).is_err());
+        assert!(parse(&["1-2", "--input", "x.txt"]).is_err());
+        assert!(parse(&["9", "--verbose"]).is_err());
+        assert!(parse(&["9", "--input", "x.txt", "--check"]).is_err());
+        assert!(parse(&["9", "--format", "yaml"]).is_err());
+        assert!(parse(&["9", "--timeout", "soon"]).is_err());
+        assert!(parse(&["9", "--jobs", "0"]).is_err());
+        assert!(parse(&["9", "--jobs", "2", "--bench"]).is_err());
+        assert!(parse(&["9", "--format", "json", "--bench"]).is_err());
+        assert!(parse(&["9", "--example", "--input", "x.txt"]).is_err());
+        assert!(parse(&["9", "--example", "--check"]).is_err());
+        assert!(parse(&["1", "--variant", "raycast"]).is_err());
+        assert!(parse(&["9", "--cross-check", "--variant", "raycast"]).is_err());
+        assert!(parse(&["9", "--cross-check", "--bench"]).is_err());
+        assert!(parse(&["8", "--param", "connections"]).is_err());
+        assert!(parse(&["8", "--param", "connections=-1"]).is_err());
+        assert!(parse(&["1", "--param", "connections=10"]).is_err());
+        assert!(parse(&["8", "--param", "connections=10", "--check"]).is_err());
+        assert!(parse(&["9", "--profile", "no-such-profile"]).is_err());
+        assert!(parse(&["9", "--profile", "default", "--input", "x.txt"]).is_err());
+        assert!(parse(&["9", "--profile", "all", "--bench"]).is_err());
+        assert!(parse(&["all", "--profile", "all", "--format", "json"]).is_err());
+    }
+}
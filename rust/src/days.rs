@@ -1,34 +1,405 @@
-pub type DayFunction = fn(&str) -> String;
+use crate::answer::{Answer, SolveError};
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
 
-pub fn noop(_: &str) -> String {
-    "Not implemented".to_string()
+/// The techniques a solution uses, for finding past solutions to crib from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    Dp,
+    Geometry,
+    Greedy,
+    Grid,
+    Intervals,
+    Math,
+    Parsing,
+    Simulation,
+    UnionFind,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 9] = [
+        Tag::Dp,
+        Tag::Geometry,
+        Tag::Greedy,
+        Tag::Grid,
+        Tag::Intervals,
+        Tag::Math,
+        Tag::Parsing,
+        Tag::Simulation,
+        Tag::UnionFind,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tag::Dp => "dp",
+            Tag::Geometry => "geometry",
+            Tag::Greedy => "greedy",
+            Tag::Grid => "grid",
+            Tag::Intervals => "intervals",
+            Tag::Math => "math",
+            Tag::Parsing => "parsing",
+            Tag::Simulation => "simulation",
+            Tag::UnionFind => "union-find",
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tag::ALL
+            .into_iter()
+            .find(|tag| tag.name() == s.to_ascii_lowercase())
+            .ok_or_else(|| {
+                let names: Vec<&str> = Tag::ALL.iter().map(|tag| tag.name()).collect();
+                format!("Unknown tag '{}', expected one of {}", s, names.join(", "))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// The status of the parts of the day00 template until they are filled in.
+    NotImplemented,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+/// What a day is about, for the catalogue printed by `list`.
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    pub title: &'static str,
+    /// One sentence on what the puzzle asks.
+    pub description: &'static str,
+    pub tags: &'static [Tag],
+    /// Implementation status of part 1 and part 2.
+    pub status: [Status; 2],
+}
+
+/// A sample input from the puzzle text, with the answers it gives.
+pub struct Example {
+    pub input: &'static str,
+    /// `None` when the puzzle gives no answer for the part.
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    /// Parameter values the puzzle text uses for this example instead of the
+    /// defaults.
+    pub params: &'static [(&'static str, usize)],
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&'static str> {
+        match part {
+            1 => self.part1,
+            _ => self.part2,
+        }
+    }
+}
+
+/// A number a solution needs that the puzzle states in its text rather than
+/// in the input, such as how many steps to simulate. The default is the
+/// value for the real input.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: usize,
+    pub description: &'static str,
+}
+
+/// The value of every parameter a day declares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(&'static str, usize)>,
+}
+
+impl Params {
+    /// The defaults of `declared`.
+    pub fn new(declared: &[Param]) -> Self {
+        Params {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default))
+                .collect(),
+        }
+    }
+
+    /// The defaults of the parameters `S` declares.
+    pub fn of<S: Solution>() -> Self {
+        Params::new(S::PARAMS)
+    }
+
+    /// Sets `name` to `value` and returns whether `name` is declared.
+    /// Undeclared names are ignored, so one set of overrides can be applied
+    /// to every selected day.
+    pub fn set(&mut self, name: &str, value: usize) -> bool {
+        match self
+            .values
+            .iter_mut()
+            .find(|(declared, _)| *declared == name)
+        {
+            Some((_, v)) => {
+                *v = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns these parameters with `name` set to `value`.
+    ///
+    /// # Panics
+    ///
+    /// If `name` is not declared.
+    pub fn with(mut self, name: &str, value: usize) -> Self {
+        assert!(self.set(name, value), "undeclared parameter '{}'", name);
+        self
+    }
+
+    /// # Panics
+    ///
+    /// If `name` is not declared.
+    pub fn get(&self, name: &str) -> usize {
+        self.values
+            .iter()
+            .find(|(declared, _)| *declared == name)
+            .map(|&(_, value)| value)
+            .unwrap_or_else(|| panic!("undeclared parameter '{}'", name))
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+/// A puzzle solution. The input is parsed once and both parts solve from the
+/// parsed value, so expensive preprocessing is shared between them.
+pub trait Solution {
+    type Parsed: Send + Sync + 'static;
+
+    const INFO: Info;
+    const EXAMPLES: &'static [Example];
+    const PARAMS: &'static [Param] = &[];
+    /// Named implementations of the parts. `part1` and `part2` should call
+    /// one of the variants of their part, if it has any.
+    const VARIANTS: &'static [Variant] = &[];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError>;
+    fn part1(parsed: &Self::Parsed, params: &Params) -> Result<Answer, SolveError>;
+    fn part2(parsed: &Self::Parsed, params: &Params) -> Result<Answer, SolveError>;
+}
+
+/// A parsed input with its type erased, so days with different `Parsed`
+/// types can share one registry.
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+pub type ParseFunction = fn(&str) -> Result<Parsed, SolveError>;
+pub type PartFunction = fn(&Parsed, &Params) -> Result<Answer, SolveError>;
+
+/// The parsed input of `S`, for variants, which see it type-erased.
+///
+/// # Panics
+///
+/// If `parsed` was not produced by `S::parse`.
+pub fn downcast<S: Solution>(parsed: &Parsed) -> &S::Parsed {
+    parsed
+        .downcast_ref()
+        .expect("parsed input was produced by the same solution")
+}
+
+/// One of several implementations of a part, such as a naive version kept to
+/// check an optimized one against.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: PartFunction,
+}
+
+/// The name of the only variant of a part that declares none.
+pub const DEFAULT_VARIANT: &str = "default";
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub parse: ParseFunction,
+    pub part1: PartFunction,
+    pub part2: PartFunction,
+    pub info: Info,
+    pub examples: &'static [Example],
+    pub params: &'static [Param],
+    pub variants: &'static [Variant],
+    /// The source of the day's module, which cached answers are keyed by.
+    /// Filled in by the registry.
+    pub source: &'static str,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self {
+        Day {
+            parse: |input| S::parse(input).map(|parsed| Arc::new(parsed) as Parsed),
+            part1: |parsed, params| S::part1(downcast::<S>(parsed), params),
+            part2: |parsed, params| S::part2(downcast::<S>(parsed), params),
+            info: S::INFO,
+            examples: S::EXAMPLES,
+            params: S::PARAMS,
+            variants: S::VARIANTS,
+            source: "",
+        }
+    }
+
+    pub fn part(&self, part: u8) -> PartFunction {
+        match part {
+            1 => self.part1,
+            _ => self.part2,
+        }
+    }
+
+    /// Every implementation of `part`, or just the default one if the part
+    /// declares no variants.
+    pub fn variants(&self, part: u8) -> Vec<Variant> {
+        let variants: Vec<Variant> = self
+            .variants
+            .iter()
+            .filter(|variant| variant.part == part)
+            .copied()
+            .collect();
+        if !variants.is_empty() {
+            return variants;
+        }
+        vec![Variant {
+            part,
+            name: DEFAULT_VARIANT,
+            solve: self.part(part),
+        }]
+    }
+
+    /// The variant of `part` called `name`, falling back to the default
+    /// implementation if there is none by that name.
+    pub fn variant(&self, part: u8, name: Option<&str>) -> PartFunction {
+        self.variants
+            .iter()
+            .find(|variant| variant.part == part && Some(variant.name) == name)
+            .map_or(self.part(part), |variant| variant.solve)
+    }
+
+    /// The name of the implementation of `part` that `variant` picks.
+    pub fn variant_name(&self, part: u8, variant: Option<&str>) -> &'static str {
+        self.variants
+            .iter()
+            .find(|v| v.part == part && Some(v.name) == variant)
+            .map_or(DEFAULT_VARIANT, |v| v.name)
+    }
+
+    pub fn has_variant(&self, name: &str) -> bool {
+        self.variants.iter().any(|variant| variant.name == name)
+    }
+
+    /// The day's parameters with the values in `overrides` that it declares.
+    pub fn params<'a>(&self, overrides: impl IntoIterator<Item = (&'a str, usize)>) -> Params {
+        let mut params = Params::new(self.params);
+        for (name, value) in overrides {
+            params.set(name, value);
+        }
+        params
+    }
+
+    pub fn declares(&self, name: &str) -> bool {
+        self.params.iter().any(|param| param.name == name)
+    }
+}
+
+/// Checks one part of `S`, and each of its variants, against every example
+/// that has an expected answer for it.
+#[cfg(test)]
+pub fn assert_examples<S: Solution>(part: u8) {
+    for (i, example) in S::EXAMPLES.iter().enumerate() {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+        let parsed = S::parse(example.input)
+            .unwrap_or_else(|e| panic!("example {} failed to parse: {}", i + 1, e));
+        let mut params = Params::of::<S>();
+        for &(name, value) in example.params {
+            params = params.with(name, value);
+        }
+        let answer = match part {
+            1 => S::part1(&parsed, &params),
+            _ => S::part2(&parsed, &params),
+        };
+        assert_eq!(
+            answer.map(|answer| answer.to_string()),
+            Ok(expected.to_string()),
+            "example {}, part {}",
+            i + 1,
+            part
+        );
+
+        let parsed: Parsed = Arc::new(parsed);
+        for variant in S::VARIANTS.iter().filter(|variant| variant.part == part) {
+            assert_eq!(
+                (variant.solve)(&parsed, &params).map(|answer| answer.to_string()),
+                Ok(expected.to_string()),
+                "example {}, part {}, variant {}",
+                i + 1,
+                part,
+                variant.name
+            );
+        }
+    }
 }
 
 macro_rules! days {
-    ($($day_num:expr => $day_mod:ident),* $(,)?) => {
+    ($($day_num:expr => $day_mod:ident :: $solution:ident in $path:literal),* $(,)?) => {
         $(
+            #[path = $path]
             pub mod $day_mod;
         )*
 
-        pub fn get_day(day: u8) -> (DayFunction, DayFunction) {
+        /// Every day that has a solution module, in ascending order.
+        pub const DAYS: &[u8] = &[$($day_num),*];
+
+        pub fn get_day(day: u8) -> Option<Day> {
             match day {
                 $(
-                    $day_num => ($day_mod::part1, $day_mod::part2),
+                    $day_num => Some(Day {
+                        source: include_str!($path),
+                        ..Day::of::<$day_mod::$solution>()
+                    }),
                 )*
-                _ => (noop, noop),
+                _ => None,
             }
         }
     };
 }
 
-days!(
-    1 => day01,
-    2 => day02,
-    3 => day03,
-    4 => day04,
-    5 => day05,
-    6 => day06,
-    7 => day07,
-    8 => day08,
-    9 => day09,
-);
+// Generated by build.rs from the `dayNN.rs` files in `src/days`.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+// The template `new` copies is not a day, but is compiled with the tests so
+// that changes to `Solution` break the build rather than the next `new`.
+#[cfg(test)]
+#[path = "days/day00.rs"]
+mod day00;
@@ -1,24 +1,53 @@
-pub fn part1(input: &str) -> String {
-    "".into()
-}
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status};
+
+// The example from the puzzle text, whose answers go in `EXAMPLES`.
+const EXAMPLE: &str = r#""#;
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    type Parsed = String;
+
+    const INFO: Info = Info {
+        title: "",
+        description: "",
+        tags: &[],
+        status: [Status::NotImplemented, Status::NotImplemented],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: None,
+        part2: None,
+        params: &[],
+    }];
 
-pub fn part2(input: &str) -> String {
-    "".into()
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(_input: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(_input: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_part1() {
-        let input = r#""#;
-        assert_eq!(part1(input), "");
+        assert_examples::<Day00>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#""#;
-        assert_eq!(part2(input), "");
+        assert_examples::<Day00>(2);
     }
 }
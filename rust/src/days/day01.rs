@@ -1,90 +1,118 @@
-fn parse_instruction(instruction: &str) -> (i32, i32) {
-    let (dir_str, distance_str) = instruction.split_at(1);
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag};
+use crate::parse::ParseError;
+
+fn parse_instruction(index: usize, line: &str) -> Result<(i32, i32), ParseError> {
+    let instruction = line.trim();
+    let dir_len = instruction.chars().next().map_or(0, char::len_utf8);
+    let (dir_str, distance_str) = instruction.split_at(dir_len);
     let direction = match dir_str {
         "L" => -1,
         "R" => 1,
-        _ => panic!("Invalid direction, expected 'L' or 'R', got {}", &dir_str),
+        _ => return Err(ParseError::new(index, line, dir_str, "'L' or 'R'")),
     };
-    let distance: i32 = distance_str.parse().unwrap_or_else(|_| {
-        panic!(
-            "Failed to parse distance '{}' in instruction '{}'",
-            distance_str, instruction
-        )
-    });
-
-    (direction, distance)
+    let distance: i32 = distance_str
+        .parse()
+        .map_err(|_| ParseError::new(index, line, distance_str, "a distance"))?;
+
+    Ok((direction, distance))
 }
 
-pub fn part1(input: &str) -> String {
-    let mut pos = 50;
-    let mut answer = 0;
+const EXAMPLE: &str = r#"L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82"#;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Vec<(i32, i32)>;
+
+    const INFO: Info = Info {
+        title: "Secret Entrance",
+        description: "Count how often a dial stops at, then passes, zero while it is turned left and right.",
+        tags: &[Tag::Simulation, Tag::Math],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("3"),
+        part2: Some("6"),
+        params: &[],
+    }];
 
-    for line in input.lines() {
-        let (direction, distance) = parse_instruction(line.trim());
-        pos += direction * distance;
-        pos %= 100;
-        if pos == 0 {
-            answer += 1;
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| parse_instruction(i, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(instructions: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut pos = 50;
+        let mut answer = 0;
+
+        for &(direction, distance) in instructions {
+            pos += direction * distance;
+            pos %= 100;
+            if pos == 0 {
+                answer += 1;
+            }
         }
+
+        Ok(answer.into())
     }
 
-    answer.to_string()
-}
+    fn part2(instructions: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut pos = 50;
+        let mut answer = 0;
 
-pub fn part2(input: &str) -> String {
-    let mut pos = 50;
-    let mut answer = 0;
+        for &(direction, distance) in instructions {
+            answer += distance / 100;
 
-    for line in input.lines() {
-        let (direction, distance) = parse_instruction(line.trim());
-        answer += distance / 100;
+            let old_pos = pos;
+            pos += direction * (distance % 100);
 
-        let old_pos = pos;
-        pos += direction * (distance % 100);
+            if old_pos != 0 && (pos <= 0 || pos >= 100) {
+                answer += 1;
+            }
 
-        if old_pos != 0 && (pos <= 0 || pos >= 100) {
-            answer += 1;
+            pos = pos.rem_euclid(100)
         }
 
-        pos = pos.rem_euclid(100)
+        Ok(answer.into())
     }
-
-    answer.to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_part1() {
-        let input = r#"L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82"#;
-
-        assert_eq!(part1(input), "3");
+        assert_examples::<Day01>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#"L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82"#;
+        assert_examples::<Day01>(2);
+    }
 
-        assert_eq!(part2(input), "6");
+    #[test]
+    fn test_parse_error() {
+        let error = Day01::parse("L68\nX30").err();
+        assert_eq!(
+            error.map(|e| e.to_string()),
+            Some("parse error: line 2, column 1: expected 'L' or 'R', found 'X'".into())
+        );
     }
 }
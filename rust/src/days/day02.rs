@@ -1,8 +1,18 @@
-fn parse_range(s: &str) -> Option<(u64, u64)> {
-    let (start, end) = s.split_once('-')?;
-    let start = start.parse::<u64>().ok()?;
-    let end = end.parse::<u64>().ok()?;
-    Some((start, end))
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag};
+use crate::parse::ParseError;
+
+fn parse_range(index: usize, line: &str, s: &str) -> Result<(u64, u64), ParseError> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or_else(|| ParseError::new(index, line, s, "a range like '11-22'"))?;
+    let start = start
+        .parse::<u64>()
+        .map_err(|_| ParseError::new(index, line, start, "a number"))?;
+    let end = end
+        .parse::<u64>()
+        .map_err(|_| ParseError::new(index, line, end, "a number"))?;
+    Ok((start, end))
 }
 
 fn num_digits(n: u64) -> u32 {
@@ -35,82 +45,117 @@ fn repeat_digits(n: u64, times: u32) -> u64 {
     result
 }
 
-pub fn part1(input: &str) -> String {
-    let mut answer: u64 = 0;
-
-    for range_str in input.split(',') {
-        let (min, max) = parse_range(range_str.trim())
-            .unwrap_or_else(|| panic!("parse range failed: '{}'", range_str));
-
-        let max = if num_digits(max).is_multiple_of(2) {
-            max
-        } else {
-            10_u64.pow(num_digits(max) - 1) - 1
-        };
-
-        let mut top = first_chunk(max, 2).unwrap();
-        let mut offset = 10_u64.pow(num_digits(max) / 2);
-
-        while top * offset + top >= min {
-            while top >= offset / 10 && (top * offset + top) >= min {
-                if top * offset + top <= max {
-                    answer += top * offset + top;
-                }
-                top -= 1;
+const EXAMPLE: &str = r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<(u64, u64)>;
+
+    const INFO: Info = Info {
+        title: "Gift Shop",
+        description: "Sum the IDs in the given ranges that are made of a repeated sequence of digits.",
+        tags: &[Tag::Math],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part1: Some("1227775554"),
+            part2: Some("4174379265"),
+            params: &[],
+        },
+        Example {
+            input: "48-84",
+            part1: Some("198"),
+            part2: None,
+            params: &[],
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        let mut ranges = vec![];
+        for (i, line) in input.lines().enumerate() {
+            for range_str in line.split(',') {
+                ranges.push(parse_range(i, line, range_str.trim())?);
             }
-            top /= 10;
-            offset /= 100;
         }
+        Ok(ranges)
     }
 
-    answer.to_string()
-}
-
-pub fn part2(input: &str) -> String {
-    let mut answer: u64 = 0;
-
-    let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
-
-    let mut counted = std::collections::HashSet::<u64>::new();
-
-    for range_str in input.split(',') {
-        let (min, max) = parse_range(range_str.trim())
-            .unwrap_or_else(|| panic!("parse range failed: '{}'", range_str));
+    fn part1(ranges: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut answer: u64 = 0;
 
-        for times in primes {
-            let max_digit_len = num_digits(max);
-            let max = if max_digit_len.is_multiple_of(times) {
+        for &(min, max) in ranges {
+            let max = if num_digits(max).is_multiple_of(2) {
                 max
             } else {
-                10_u64.pow(max_digit_len - (max_digit_len % times)) - 1
+                10_u64.pow(num_digits(max) - 1) - 1
             };
 
-            let mut top = first_chunk(max, times).unwrap();
-            let mut offset = 10_u64.pow(num_digits(max) / times);
+            let mut top = first_chunk(max, 2).unwrap();
+            let mut offset = 10_u64.pow(num_digits(max) / 2);
 
-            let mut val = repeat_digits(top, times);
-            while val >= min {
-                while top >= offset / 10 && val >= min {
-                    if val <= max && !counted.contains(&val) {
-                        counted.insert(val);
-                        answer += val;
+            while top * offset + top >= min {
+                while top >= offset / 10 && (top * offset + top) >= min {
+                    if top * offset + top <= max {
+                        answer += top * offset + top;
                     }
                     top -= 1;
-                    val = repeat_digits(top, times);
                 }
                 top /= 10;
-                offset /= 10_u64.pow(times);
-                val = repeat_digits(top, times);
+                offset /= 100;
             }
         }
+
+        Ok(answer.into())
     }
 
-    answer.to_string()
+    fn part2(ranges: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut answer: u64 = 0;
+
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+        let mut counted = std::collections::HashSet::<u64>::new();
+
+        for &(min, max) in ranges {
+            for times in primes {
+                let max_digit_len = num_digits(max);
+                let max = if max_digit_len.is_multiple_of(times) {
+                    max
+                } else {
+                    10_u64.pow(max_digit_len - (max_digit_len % times)) - 1
+                };
+
+                let mut top = first_chunk(max, times).unwrap();
+                let mut offset = 10_u64.pow(num_digits(max) / times);
+
+                let mut val = repeat_digits(top, times);
+                while val >= min {
+                    while top >= offset / 10 && val >= min {
+                        if val <= max && !counted.contains(&val) {
+                            counted.insert(val);
+                            answer += val;
+                        }
+                        top -= 1;
+                        val = repeat_digits(top, times);
+                    }
+                    top /= 10;
+                    offset /= 10_u64.pow(times);
+                    val = repeat_digits(top, times);
+                }
+            }
+        }
+
+        Ok(answer.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_num_digits() {
@@ -131,15 +176,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = r#"48-84"#;
-        assert_eq!(part1(input), (55 + 66 + 77).to_string());
-        let input = r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#;
-        assert_eq!(part1(input), "1227775554");
+        assert_examples::<Day02>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"#;
-        assert_eq!(part2(input), "4174379265");
+        assert_examples::<Day02>(2);
     }
 }
@@ -1,87 +1,183 @@
-fn parse_bank(s: &str) -> Vec<u32> {
-    s.chars().map(|c| c.to_digit(10).unwrap()).collect()
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Param, Params, Solution, Status, Tag};
+use crate::parse::ParseError;
+
+fn parse_bank(index: usize, line: &str) -> Result<Vec<u32>, ParseError> {
+    line.char_indices()
+        .map(|(col, c)| {
+            c.to_digit(10).ok_or_else(|| {
+                ParseError::new(index, line, &line[col..col + c.len_utf8()], "a digit")
+            })
+        })
+        .collect()
 }
 
-pub fn part1(input: &str) -> String {
-    let mut answer = 0;
-
-    for line in input.lines() {
-        let bank = parse_bank(line);
-        let mut max_first_digit: u32 = 0;
-        let mut first_digit_idx: usize = 0;
-        for (i, n) in bank[0..bank.len() - 1].iter().enumerate() {
-            if *n > max_first_digit {
-                max_first_digit = *n;
-                first_digit_idx = i;
-            }
+const EXAMPLE: &str = r#"987654321111111
+811111111111119
+234234234234278
+818181911112111"#;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Vec<Vec<u32>>;
+
+    const INFO: Info = Info {
+        title: "Lobby",
+        description: "Pick the batteries in each bank that give the largest joltage.",
+        tags: &[Tag::Greedy],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE,
+            part1: Some("357"),
+            part2: Some("3121910778619"),
+            params: &[],
+        },
+        Example {
+            input: "987654321111111",
+            part1: None,
+            part2: Some("987654321111"),
+            params: &[],
+        },
+        Example {
+            input: "811111111111119",
+            part1: None,
+            part2: Some("811111111119"),
+            params: &[],
+        },
+    ];
+
+    const PARAMS: &'static [Param] = &[Param {
+        name: "batteries",
+        default: 12,
+        description: "how many batteries to turn on in each bank in part 2",
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| parse_bank(i, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(banks: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        if banks.iter().any(|bank| bank.len() < 2) {
+            return Err(SolveError::Unsolvable(
+                "a bank has fewer than 2 batteries".into(),
+            ));
         }
-        let mut max_second_digit = 0;
-        for n in bank[(first_digit_idx + 1)..].iter() {
-            if n > &max_second_digit {
-                max_second_digit = *n;
+
+        let mut answer = 0;
+
+        for bank in banks {
+            let mut max_first_digit: u32 = 0;
+            let mut first_digit_idx: usize = 0;
+            for (i, n) in bank[0..bank.len() - 1].iter().enumerate() {
+                if *n > max_first_digit {
+                    max_first_digit = *n;
+                    first_digit_idx = i;
+                }
+            }
+            let mut max_second_digit = 0;
+            for n in bank[(first_digit_idx + 1)..].iter() {
+                if n > &max_second_digit {
+                    max_second_digit = *n;
+                }
             }
+            let joltage = max_first_digit * 10 + max_second_digit;
+            answer += joltage
         }
-        let joltage = max_first_digit * 10 + max_second_digit;
-        answer += joltage
+
+        Ok(answer.into())
     }
 
-    answer.to_string()
-}
+    fn part2(banks: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        let batteries = params.get("batteries");
+        if batteries > 19 {
+            return Err(SolveError::Unsolvable(format!(
+                "a joltage of {} digits does not fit in 64 bits",
+                batteries
+            )));
+        }
+        if banks.iter().any(|bank| bank.len() < batteries) {
+            return Err(SolveError::Unsolvable(format!(
+                "a bank has fewer than {} batteries",
+                batteries
+            )));
+        }
 
-pub fn part2(input: &str) -> String {
-    let mut answer = 0;
-
-    for line in input.lines() {
-        let bank = parse_bank(line);
-        let mut skip_amount: usize = 0;
-        let mut joltage: u64 = 0;
-        for offset in (0..12).rev() {
-            let mut max_digit: u32 = 0;
-            for (i, n) in bank[..(bank.len() - offset)]
-                .iter()
-                .enumerate()
-                .skip(skip_amount)
-            {
-                if *n > max_digit {
-                    max_digit = *n;
-                    skip_amount = i + 1;
+        let mut answer = 0;
+
+        for bank in banks {
+            let mut skip_amount: usize = 0;
+            let mut joltage: u64 = 0;
+            for offset in (0..batteries).rev() {
+                let mut max_digit: u32 = 0;
+                for (i, n) in bank[..(bank.len() - offset)]
+                    .iter()
+                    .enumerate()
+                    .skip(skip_amount)
+                {
+                    if *n > max_digit {
+                        max_digit = *n;
+                        skip_amount = i + 1;
+                    }
                 }
+                joltage += (max_digit as u64) * 10_u64.pow(offset as u32);
             }
-            joltage += (max_digit as u64) * 10_u64.pow(offset as u32);
+            answer += joltage;
         }
-        answer += joltage;
-    }
 
-    answer.to_string()
+        Ok(answer.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_parse_bank() {
-        assert_eq!(parse_bank("1"), vec![1]);
-        assert_eq!(parse_bank("123"), vec![1, 2, 3]);
+        assert_eq!(parse_bank(0, "1"), Ok(vec![1]));
+        assert_eq!(parse_bank(0, "123"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_bank(0, "12a").map_err(|e| e.column), Err(3));
     }
 
     #[test]
     fn test_part1() {
-        let input = r#"987654321111111
-811111111111119
-234234234234278
-818181911112111"#;
-        assert_eq!(part1(input), "357");
+        assert_examples::<Day03>(1);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2("987654321111111"), "987654321111");
-        assert_eq!(part2("811111111111119"), "811111111119");
-        let input = r#"987654321111111
-811111111111119
-234234234234278
-818181911112111"#;
-        assert_eq!(part2(input), "3121910778619");
+        assert_examples::<Day03>(2);
+    }
+
+    #[test]
+    fn test_batteries() {
+        let banks = Day03::parse(EXAMPLE).unwrap();
+        let params = Params::of::<Day03>().with("batteries", 2);
+        assert_eq!(Day03::part2(&banks, &params), Ok(Answer::Int(357)));
+        let params = Params::of::<Day03>().with("batteries", 16);
+        assert!(Day03::part2(&banks, &params).is_err());
+    }
+
+    #[test]
+    fn test_short_banks() {
+        let params = Params::of::<Day03>();
+        for input in ["12\n\n34", "12\n5"] {
+            let banks = Day03::parse(input).unwrap();
+            assert_eq!(
+                Day03::part1(&banks, &params),
+                Err(SolveError::Unsolvable(
+                    "a bank has fewer than 2 batteries".into()
+                ))
+            );
+        }
     }
 }
@@ -1,198 +1,127 @@
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag};
+use crate::utils::grid::{Grid, Pos};
 use std::collections::BTreeSet;
+use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
-enum Cell {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
     Empty,
     Paper,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Copy)]
-struct Coord {
-    row: i32,
-    col: i32,
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => write!(f, "."),
+            Cell::Paper => write!(f, "@"),
+        }
+    }
 }
 
-type Cells = Vec<Vec<Cell>>;
+pub type Diagram = Grid<Cell>;
 
-struct Diagram {
-    cells: Cells,
+/// The number of paper rolls around `pos`.
+fn paper_neighbors(diagram: &Diagram, pos: Pos) -> usize {
+    diagram
+        .neighbors8(pos)
+        .filter(|&n| diagram[n] == Cell::Paper)
+        .count()
 }
 
-impl Diagram {
-    #[allow(dead_code)]
-    fn print(&self) {
-        for row in self.cells.iter() {
-            for cell in row.iter() {
-                match cell {
-                    Cell::Empty => print!("."),
-                    Cell::Paper => print!("@"),
-                }
-            }
-            println!();
-        }
-    }
+const EXAMPLE: &str = r#"..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+"#;
 
-    fn dimensions(&self) -> (usize, usize) {
-        // rows, cols
-        (self.cells.len(), self.cells[0].len())
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Diagram;
+
+    const INFO: Info = Info {
+        title: "Printing Department",
+        description: "Count the paper rolls a forklift can reach, then keep removing them until none can be.",
+        tags: &[Tag::Grid, Tag::Simulation],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("13"),
+        part2: Some("43"),
+        params: &[],
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(Grid::parse(input, "'.' or '@'", |c| match c {
+            '.' => Some(Cell::Empty),
+            '@' => Some(Cell::Paper),
+            _ => None,
+        })?)
     }
 
-    fn set_empty(&mut self, c: Coord) {
-        self.cells[c.row as usize][c.col as usize] = Cell::Empty;
-    }
+    fn part1(diagram: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let answer = diagram
+            .iter()
+            .filter(|&(pos, &cell)| cell == Cell::Paper && paper_neighbors(diagram, pos) < 4)
+            .count();
 
-    fn neighbors<'a>(&'a self, c: Coord) -> impl Iterator<Item = Cell> + 'a {
-        let (rows, cols) = self.dimensions();
-        let (r, k) = (c.row, c.col);
-
-        [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ]
-        .into_iter()
-        .map(move |(dx, dy)| (r + dx, k + dy))
-        .filter(move |&(nx, ny)| nx >= 0 && nx < rows as i32 && ny >= 0 && ny < cols as i32)
-        .map(|(x, y)| self.cells[x as usize][y as usize])
+        Ok(answer.into())
     }
-}
 
-fn parse_diagram(input: &str) -> Diagram {
-    let cells: Cells = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Cell::Empty,
-                    '@' => Cell::Paper,
-                    _ => panic!("unexpected character"),
-                })
-                .collect()
-        })
-        .collect();
-    Diagram { cells }
-}
-
-pub fn part1(input: &str) -> String {
-    let diagram = parse_diagram(input);
-    let mut answer = 0;
-
-    let (rows, cols) = diagram.dimensions();
-
-    for (r, row) in diagram.cells.iter().enumerate().take(rows) {
-        for (c, col) in row.iter().enumerate().take(cols) {
-            match col {
-                Cell::Empty => {}
-                Cell::Paper => {
-                    let n = diagram
-                        .neighbors(Coord {
-                            row: r as i32,
-                            col: c as i32,
-                        })
-                        .filter(|cell| matches!(cell, Cell::Paper))
-                        .count();
-                    if n < 4 {
-                        answer += 1;
-                    }
-                }
+    fn part2(diagram: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut diagram = diagram.clone();
+        let mut answer = 0;
+
+        let mut all_paper_coords: BTreeSet<Pos> = diagram
+            .iter()
+            .filter(|&(_, &cell)| cell == Cell::Paper)
+            .map(|(pos, _)| pos)
+            .collect();
+
+        loop {
+            let to_remove: Vec<Pos> = all_paper_coords
+                .iter()
+                .copied()
+                .filter(|&pos| paper_neighbors(&diagram, pos) < 4)
+                .collect();
+
+            if to_remove.is_empty() {
+                break;
             }
-        }
-    }
 
-    answer.to_string()
-}
-
-pub fn part2(input: &str) -> String {
-    let mut diagram = parse_diagram(input);
-    let mut answer = 0;
-
-    let mut all_paper_coords = BTreeSet::<Coord>::new();
-
-    let (rows, cols) = diagram.dimensions();
-
-    for (r, row) in diagram.cells.iter().enumerate().take(rows) {
-        for (c, col) in row.iter().enumerate().take(cols) {
-            match col {
-                Cell::Empty => {}
-                Cell::Paper => {
-                    all_paper_coords.insert(Coord {
-                        row: r as i32,
-                        col: c as i32,
-                    });
-                }
-            }
-        }
-    }
+            answer += to_remove.len();
 
-    loop {
-        let mut to_remove: Vec<Coord> = vec![];
-
-        for paper_coord in all_paper_coords.iter() {
-            let n = diagram
-                .neighbors(Coord {
-                    row: paper_coord.row,
-                    col: paper_coord.col,
-                })
-                .filter(|cell| matches!(cell, Cell::Paper))
-                .count();
-            if n < 4 {
-                to_remove.push(*paper_coord);
+            for coord in to_remove {
+                all_paper_coords.remove(&coord);
+                diagram[coord] = Cell::Empty;
             }
         }
 
-        if to_remove.is_empty() {
-            break;
-        }
-
-        answer += to_remove.len();
-
-        for coord in to_remove.iter() {
-            all_paper_coords.remove(coord);
-            diagram.set_empty(*coord);
-        }
+        Ok(answer.into())
     }
-
-    answer.to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_part1() {
-        let input = r#"..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.
-"#;
-        assert_eq!(part1(input), "13");
+        assert_examples::<Day04>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#"..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.
-"#;
-        assert_eq!(part2(input), "43");
+        assert_examples::<Day04>(2);
     }
 }
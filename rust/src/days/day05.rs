@@ -1,109 +1,138 @@
-struct Database {
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag};
+use crate::parse::ParseError;
+
+pub struct Database {
     fresh_ranges: Vec<(u64, u64)>,
     ingredients: Vec<u64>,
 }
 
-fn parse_database(input: &str) -> Database {
+fn parse_database(input: &str) -> Result<Database, ParseError> {
     let mut fresh_ranges = vec![];
     let mut ingredients = vec![];
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             continue;
         }
+        let number = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|_| ParseError::new(i, line, s, "a number"))
+        };
         match line.split_once('-') {
             Some((start, end)) => {
-                let start: u64 = start.parse().unwrap();
-                let end: u64 = end.parse().unwrap();
-                fresh_ranges.push((start, end));
+                fresh_ranges.push((number(start)?, number(end)?));
             }
             None => {
-                if let Ok(ingredient) = line.parse() {
-                    ingredients.push(ingredient);
-                }
+                let ingredient = line.parse().map_err(|_| {
+                    ParseError::new(i, line, line, "an ingredient ID or a range like '3-5'")
+                })?;
+                ingredients.push(ingredient);
             }
         }
     }
 
-    Database {
+    Ok(Database {
         fresh_ranges,
         ingredients,
-    }
+    })
 }
 
-pub fn part1(input: &str) -> String {
-    let db = parse_database(input);
-    let mut answer = 0;
+const EXAMPLE: &str = r#"3-5
+10-14
+16-20
+12-18
 
-    for ingredient in db.ingredients {
-        if db
-            .fresh_ranges
-            .iter()
-            .any(|(start, end)| (*start..=*end).contains(&ingredient))
-        {
-            answer += 1;
-        }
-    }
+1
+5
+8
+11
+17
+32"#;
 
-    answer.to_string()
-}
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = Database;
 
-pub fn part2(input: &str) -> String {
-    let mut db = parse_database(input);
+    const INFO: Info = Info {
+        title: "Cafeteria",
+        description: "Check ingredient IDs against ranges of fresh IDs, then count every fresh ID.",
+        tags: &[Tag::Intervals],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("3"),
+        part2: Some("14"),
+        params: &[],
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(parse_database(input)?)
+    }
 
-    db.fresh_ranges
-        .sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    fn part1(db: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut answer = 0;
 
-    let mut merged_ranges: Vec<(u64, u64)> = vec![];
-    for range in db.fresh_ranges {
-        if let Some(last) = merged_ranges.last_mut()
-            && range.0 <= last.1 + 1
-        {
-            last.1 = last.1.max(range.1);
-        } else {
-            merged_ranges.push(range);
+        for ingredient in &db.ingredients {
+            if db
+                .fresh_ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(ingredient))
+            {
+                answer += 1;
+            }
         }
+
+        Ok(answer.into())
     }
 
-    merged_ranges
-        .iter()
-        .map(|(start, end)| end - start + 1)
-        .sum::<u64>()
-        .to_string()
+    fn part2(db: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut fresh_ranges = db.fresh_ranges.clone();
+
+        fresh_ranges.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut merged_ranges: Vec<(u64, u64)> = vec![];
+        for range in fresh_ranges {
+            if let Some(last) = merged_ranges.last_mut()
+                && range.0 <= last.1 + 1
+            {
+                last.1 = last.1.max(range.1);
+            } else {
+                merged_ranges.push(range);
+            }
+        }
+
+        Ok(merged_ranges
+            .iter()
+            .map(|(start, end)| end - start + 1)
+            .sum::<u64>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_part1() {
-        let input = r#"3-5
-10-14
-16-20
-12-18
-
-1
-5
-8
-11
-17
-32"#;
-        assert_eq!(part1(input), "3");
+        assert_examples::<Day05>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#"3-5
-10-14
-16-20
-12-18
+        assert_examples::<Day05>(2);
+    }
 
-1
-5
-8
-11
-17
-32"#;
-        assert_eq!(part2(input), "14");
+    #[test]
+    fn test_parse_error() {
+        let error = Day05::parse("3-5\n10-1x\n\n1").err();
+        assert_eq!(
+            error.map(|e| e.to_string()),
+            Some("parse error: line 2, column 4: expected a number, found '1x'".into())
+        );
     }
 }
@@ -1,25 +1,21 @@
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag};
+use crate::parse::ParseError;
+
 #[derive(Debug, Clone, Copy)]
 enum Operation {
     Add,
     Multiply,
 }
 
-impl From<&str> for Operation {
-    fn from(s: &str) -> Self {
-        match s {
-            "+" => Operation::Add,
-            "*" => Operation::Multiply,
-            _ => panic!("Unknown operation"),
-        }
-    }
-}
+impl TryFrom<char> for Operation {
+    type Error = ();
 
-impl From<char> for Operation {
-    fn from(c: char) -> Self {
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '+' => Operation::Add,
-            '*' => Operation::Multiply,
-            _ => panic!("Unknown operation"),
+            '+' => Ok(Operation::Add),
+            '*' => Ok(Operation::Multiply),
+            _ => Err(()),
         }
     }
 }
@@ -46,44 +42,82 @@ impl Problem {
     }
 }
 
-fn parse_problems_1(input: &str) -> Vec<Problem> {
-    let num_problems = input.lines().next().unwrap().split_whitespace().count();
+/// The problems on the worksheet, read the two ways the parts need them.
+pub struct Worksheet {
+    /// Numbers written left to right along the rows.
+    by_rows: Vec<Problem>,
+    /// Numbers written top to bottom along the columns, right to left.
+    by_columns: Vec<Problem>,
+}
+
+fn parse_problems_1(input: &str) -> Result<Vec<Problem>, ParseError> {
+    let first_line = input
+        .lines()
+        .next()
+        .ok_or_else(|| ParseError::empty_input("a row of numbers"))?;
+    let num_problems = first_line.split_whitespace().count();
     let mut problems: Vec<Problem> = Vec::with_capacity(num_problems);
 
     for _ in 0..num_problems {
         problems.push(Problem::new());
     }
 
-    for line in input.lines() {
+    let mut last_line = (0, first_line);
+    for (line_idx, line) in input.lines().enumerate() {
+        last_line = (line_idx, line);
         for (i, tok) in line.split_whitespace().enumerate() {
+            let Some(problem) = problems.get_mut(i) else {
+                return Err(ParseError::new(
+                    line_idx,
+                    line,
+                    tok,
+                    format!("at most {} problems per row", num_problems),
+                ));
+            };
             if let Ok(n) = tok.parse::<u64>() {
-                problems[i].numbers.push(n);
+                problem.numbers.push(n);
             } else {
-                let op = Some(tok.into());
-                problems[i].operation = op;
+                let mut chars = tok.chars();
+                let op = match (chars.next(), chars.next()) {
+                    (Some(c), None) => Operation::try_from(c).ok(),
+                    _ => None,
+                };
+                let op = op.ok_or_else(|| ParseError::new(line_idx, line, tok, "a number, '+' or '*'"))?;
+                problem.operation = Some(op);
             }
         }
     }
 
-    problems
-}
+    if problems.iter().any(|problem| problem.operation.is_none()) {
+        let (line_idx, line) = last_line;
+        return Err(ParseError::new(
+            line_idx,
+            line,
+            line,
+            "an operation for every problem",
+        ));
+    }
 
-fn parse_problems_2(input: &str) -> Vec<Problem> {
-    let last_line = input.lines().last().unwrap();
-    let mut problems: Vec<Problem> = Vec::with_capacity(last_line.split_whitespace().count());
+    Ok(problems)
+}
 
-    let mut op_pos: Vec<usize> = last_line
-        .chars()
+fn parse_problems_2(input: &str) -> Result<Vec<Problem>, ParseError> {
+    let (last_idx, last_line) = input
+        .lines()
         .enumerate()
-        .filter(|&(_, c)| c != ' ')
-        .map(|(i, _)| i)
-        .collect();
+        .last()
+        .ok_or_else(|| ParseError::empty_input("a row of operations"))?;
+    let mut problems: Vec<Problem> = Vec::with_capacity(last_line.split_whitespace().count());
 
-    let last_line_chars: Vec<char> = last_line.chars().collect();
-    for i in &op_pos {
+    let mut op_pos: Vec<usize> = vec![];
+    for (i, c) in last_line.char_indices().filter(|&(_, c)| c != ' ') {
+        let operation = Operation::try_from(c).map_err(|_| {
+            ParseError::new(last_idx, last_line, &last_line[i..i + c.len_utf8()], "'+' or '*'")
+        })?;
+        op_pos.push(i);
         problems.push(Problem {
             numbers: Vec::new(),
-            operation: Some(last_line_chars[*i].into()),
+            operation: Some(operation),
         });
     }
 
@@ -99,7 +133,7 @@ fn parse_problems_2(input: &str) -> Vec<Problem> {
             let mut n = 0;
             let mut scale = 1;
             for line in &lines_reversed {
-                if let Some(c) = (line.as_bytes()[i] as char).to_digit(10) {
+                if let Some(c) = line.as_bytes().get(i).and_then(|&b| (b as char).to_digit(10)) {
                     n += c as u64 * scale;
                     scale *= 10;
                 }
@@ -110,47 +144,71 @@ fn parse_problems_2(input: &str) -> Vec<Problem> {
         problems[i].numbers = numbers;
     }
 
-    problems
-}
-pub fn part1(input: &str) -> String {
-    let problems = parse_problems_1(input);
-
-    problems
-        .iter()
-        .map(|problem| problem.solve())
-        .sum::<u64>()
-        .to_string()
+    Ok(problems)
 }
 
-pub fn part2(input: &str) -> String {
-    let problems = parse_problems_2(input);
+const EXAMPLE: &str = r#"123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  "#;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Worksheet;
+
+    const INFO: Info = Info {
+        title: "Trash Compactor",
+        description: "Solve the worksheet problems read along the rows, then along the columns.",
+        tags: &[Tag::Parsing],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("4277556"),
+        part2: Some("3263827"),
+        params: &[],
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(Worksheet {
+            by_rows: parse_problems_1(input)?,
+            by_columns: parse_problems_2(input)?,
+        })
+    }
 
-    problems
-        .iter()
-        .map(|problem| problem.solve())
-        .sum::<u64>()
-        .to_string()
+    fn part1(worksheet: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(worksheet
+            .by_rows
+            .iter()
+            .map(|problem| problem.solve())
+            .sum::<u64>()
+            .into())
+    }
+
+    fn part2(worksheet: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        Ok(worksheet
+            .by_columns
+            .iter()
+            .map(|problem| problem.solve())
+            .sum::<u64>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_part1() {
-        let input = r#"123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  "#;
-        assert_eq!(part1(input), "4277556");
+        assert_examples::<Day06>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#"123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  "#;
-        assert_eq!(part2(input), "3263827");
+        assert_examples::<Day06>(2);
     }
 }
@@ -1,35 +1,66 @@
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag};
+use crate::parse::ParseError;
+use crate::utils::grid::Grid;
 use std::{
     collections::{HashMap, HashSet},
     str::FromStr,
 };
 
-struct Diagram {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Start,
+    Splitter,
+}
+
+pub struct Diagram {
     beam_start: (usize, usize),
     splitters: Vec<Vec<usize>>,
 }
 
 impl FromStr for Diagram {
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut beam_start = None;
-        let mut splitters = vec![vec![]; input.lines().next().unwrap().chars().count()];
-
-        for (i, line) in input.lines().enumerate() {
-            for (j, ch) in line.chars().enumerate() {
-                if ch == 'S' {
-                    beam_start = Some((i, j));
-                } else if ch == '^' {
-                    splitters[j].push(i);
-                }
-            }
+        let grid = Grid::parse(input, "'.', 'S' or '^'", |c| match c {
+            '.' => Some(Cell::Empty),
+            'S' => Some(Cell::Start),
+            '^' => Some(Cell::Splitter),
+            _ => None,
+        })?;
+
+        let mut starts = grid.iter().filter(|&(_, &cell)| cell == Cell::Start);
+        let (start, _) = starts.next().ok_or_else(|| {
+            let line = input.lines().next().unwrap();
+            ParseError::new(0, line, &line[..0], "a beam start 'S'")
+        })?;
+        if let Some((other, _)) = starts.next() {
+            let line = input.lines().nth(other.y).unwrap();
+            let (offset, _) = line.char_indices().nth(other.x).unwrap();
+            return Err(ParseError::new(
+                other.y,
+                line,
+                &line[offset..offset + 1],
+                "a single beam start",
+            ));
         }
 
+        let splitters = grid
+            .columns()
+            .map(|column| {
+                column
+                    .enumerate()
+                    .filter(|&(_, &cell)| cell == Cell::Splitter)
+                    .map(|(row, _)| row)
+                    .collect()
+            })
+            .collect();
         Ok(Diagram {
-            beam_start: beam_start.unwrap(),
+            beam_start: (start.y, start.x),
             splitters,
         })
     }
 
-    type Err = ();
+    type Err = ParseError;
 }
 
 impl Diagram {
@@ -50,34 +81,6 @@ impl Diagram {
     }
 }
 
-pub fn part1(input: &str) -> String {
-    let diagram: Diagram = input.parse().unwrap();
-    let mut beams = vec![diagram.beam_start];
-    let mut splitter_hit: HashSet<(usize, usize)> = HashSet::new();
-    let mut tested_beams: HashSet<(usize, usize)> = HashSet::new();
-
-    while let Some(beam) = beams.pop() {
-        if tested_beams.contains(&beam) {
-            continue;
-        }
-
-        if let Some(splitter) = diagram.find_splitter_below(beam) {
-            splitter_hit.insert(splitter);
-            let (r, c) = splitter;
-            if c > 0 {
-                beams.push((r, c - 1));
-            }
-            if c + 1 < diagram.splitters.len() {
-                beams.push((r, c + 1));
-            }
-        }
-
-        tested_beams.insert(beam);
-    }
-
-    splitter_hit.len().to_string()
-}
-
 fn timelines(
     cache: &mut HashMap<(usize, usize), usize>,
     diagram: &Diagram,
@@ -105,20 +108,7 @@ fn timelines(
     count
 }
 
-pub fn part2(input: &str) -> String {
-    let diagram: Diagram = input.parse().unwrap();
-    let mut cache: HashMap<(usize, usize), usize> = HashMap::new();
-
-    timelines(&mut cache, &diagram, diagram.beam_start).to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let input = r#".......S.......
+const EXAMPLE: &str = r#".......S.......
 ...............
 .......^.......
 ...............
@@ -134,27 +124,76 @@ mod tests {
 ...............
 .^.^.^.^.^...^.
 ..............."#;
-        assert_eq!(part1(input), "21");
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Diagram;
+
+    const INFO: Info = Info {
+        title: "Laboratories",
+        description: "Count how often a tachyon beam is split, then how many timelines the splits create.",
+        tags: &[Tag::Grid, Tag::Dp],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("21"),
+        part2: Some("40"),
+        params: &[],
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(diagram: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut beams = vec![diagram.beam_start];
+        let mut splitter_hit: HashSet<(usize, usize)> = HashSet::new();
+        let mut tested_beams: HashSet<(usize, usize)> = HashSet::new();
+
+        while let Some(beam) = beams.pop() {
+            if tested_beams.contains(&beam) {
+                continue;
+            }
+
+            if let Some(splitter) = diagram.find_splitter_below(beam) {
+                splitter_hit.insert(splitter);
+                let (r, c) = splitter;
+                if c > 0 {
+                    beams.push((r, c - 1));
+                }
+                if c + 1 < diagram.splitters.len() {
+                    beams.push((r, c + 1));
+                }
+            }
+
+            tested_beams.insert(beam);
+        }
+
+        Ok(splitter_hit.len().into())
+    }
+
+    fn part2(diagram: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut cache: HashMap<(usize, usize), usize> = HashMap::new();
+
+        Ok(timelines(&mut cache, diagram, diagram.beam_start).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::assert_examples;
+
+    #[test]
+    fn test_part1() {
+        assert_examples::<Day07>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-..............."#;
-        assert_eq!(part2(input), "40");
+        assert_examples::<Day07>(2);
     }
 }
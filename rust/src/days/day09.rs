@@ -1,63 +1,12 @@
-#[allow(dead_code, unused_variables)]
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
-use std::str::FromStr;
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coordinate {
-    x: u64,
-    y: u64,
-}
-
-impl Coordinate {
-    fn neighbors(&self) -> Vec<Coordinate> {
-        let mut neighbors = Vec::new();
-        if self.x > 0 {
-            neighbors.push(Coordinate {
-                x: self.x - 1,
-                y: self.y,
-            });
-        }
-        neighbors.push(Coordinate {
-            x: self.x + 1,
-            y: self.y,
-        });
-        if self.y > 0 {
-            neighbors.push(Coordinate {
-                x: self.x,
-                y: self.y - 1,
-            });
-        }
-        neighbors.push(Coordinate {
-            x: self.x,
-            y: self.y + 1,
-        });
-        neighbors
-    }
-}
-
-struct ParseCoordinateError;
-
-impl FromStr for Coordinate {
-    type Err = ParseCoordinateError;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = input.trim().split(',').collect();
-        if parts.len() != 2 {
-            return Err(ParseCoordinateError);
-        }
-        let x = parts[0].parse::<u64>().map_err(|_| ParseCoordinateError)?;
-        let y = parts[1].parse::<u64>().map_err(|_| ParseCoordinateError)?;
-        Ok(Coordinate { x, y })
-    }
-}
-
-impl Coordinate {
-    fn rectangle_area(&self, other: &Coordinate) -> u64 {
-        let dx = (self.x as i64 - other.x as i64).abs() + 1;
-        let dy = (self.y as i64 - other.y as i64).abs() + 1;
-        (dx.abs() * dy.abs()) as u64
-    }
+use crate::answer::{Answer, SolveError};
+use crate::days::{Example, Info, Params, Solution, Status, Tag, Variant, downcast};
+use crate::utils::point::Point2;
+use std::collections::{BinaryHeap, HashMap};
+
+/// The number of tiles in the rectangle with opposite corners `a` and `b`,
+/// inclusive.
+pub fn rectangle_area(a: &Point2<u64>, b: &Point2<u64>) -> u64 {
+    (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
 }
 
 struct AreaEntry {
@@ -112,8 +61,7 @@ enum Direction {
 }
 
 impl Bounds {
-    #[allow(dead_code)]
-    fn closest_edge(&self, c: &Coordinate) -> Direction {
+    fn closest_edge(&self, c: &Point2<u64>) -> Direction {
         let dist_left = c.x - self.x_min;
         let dist_right = self.x_max - c.x;
         let dist_top = c.y - self.y_min;
@@ -133,15 +81,18 @@ impl Bounds {
     }
 }
 
+/// A horizontal or vertical stretch of the border, from one end to the other.
+type Segment = (Point2<u64>, Point2<u64>);
+
 struct Raycaster {
-    tiles: Vec<Coordinate>,
-    horizontal_line_segments: HashMap<usize, Vec<(Coordinate, Coordinate)>>,
-    vertical_line_segments: HashMap<usize, Vec<(Coordinate, Coordinate)>>,
+    tiles: Vec<Point2<u64>>,
+    horizontal_line_segments: HashMap<usize, Vec<Segment>>,
+    vertical_line_segments: HashMap<usize, Vec<Segment>>,
     bounds: Bounds,
 }
 
 impl Raycaster {
-    fn new(tiles: Vec<Coordinate>) -> Self {
+    fn new(tiles: Vec<Point2<u64>>) -> Self {
         let bounds = tiles.iter().fold(Bounds::default(), |mut bounds, tile| {
             if tile.x < bounds.x_min {
                 bounds.x_min = tile.x;
@@ -158,9 +109,9 @@ impl Raycaster {
             bounds
         });
 
-        let mut horizontal_line_segments: HashMap<usize, Vec<(Coordinate, Coordinate)>> =
+        let mut horizontal_line_segments: HashMap<usize, Vec<Segment>> =
             HashMap::new();
-        let mut vertical_line_segments: HashMap<usize, Vec<(Coordinate, Coordinate)>> =
+        let mut vertical_line_segments: HashMap<usize, Vec<Segment>> =
             HashMap::new();
 
         let wrapped_tiles = {
@@ -176,15 +127,15 @@ impl Raycaster {
                 let (x_start, x_end) = if a.x < b.x { (a.x, b.x) } else { (b.x, a.x) };
                 if let Some(segments) = horizontal_line_segments.get_mut(&(a.y as usize)) {
                     segments.push((
-                        Coordinate { x: x_start, y: a.y },
-                        Coordinate { x: x_end, y: a.y },
+                        Point2 { x: x_start, y: a.y },
+                        Point2 { x: x_end, y: a.y },
                     ));
                 } else {
                     horizontal_line_segments.insert(
                         a.y as usize,
                         vec![(
-                            Coordinate { x: x_start, y: a.y },
-                            Coordinate { x: x_end, y: a.y },
+                            Point2 { x: x_start, y: a.y },
+                            Point2 { x: x_end, y: a.y },
                         )],
                     );
                 }
@@ -192,15 +143,15 @@ impl Raycaster {
                 let (y_start, y_end) = if a.y < b.y { (a.y, b.y) } else { (b.y, a.y) };
                 if let Some(segments) = vertical_line_segments.get_mut(&(a.x as usize)) {
                     segments.push((
-                        Coordinate { x: a.x, y: y_start },
-                        Coordinate { x: a.x, y: y_end },
+                        Point2 { x: a.x, y: y_start },
+                        Point2 { x: a.x, y: y_end },
                     ));
                 } else {
                     vertical_line_segments.insert(
                         a.x as usize,
                         vec![(
-                            Coordinate { x: a.x, y: y_start },
-                            Coordinate { x: a.x, y: y_end },
+                            Point2 { x: a.x, y: y_start },
+                            Point2 { x: a.x, y: y_end },
                         )],
                     );
                 }
@@ -215,7 +166,7 @@ impl Raycaster {
         }
     }
 
-    fn is_on_border(&self, coord: &Coordinate) -> bool {
+    fn is_on_border(&self, coord: &Point2<u64>) -> bool {
         for seg in self
             .horizontal_line_segments
             .get(&(coord.y as usize))
@@ -237,7 +188,7 @@ impl Raycaster {
         false
     }
 
-    fn is_enclosed(&self, coord: &Coordinate, cache: &mut HashMap<Coordinate, bool>) -> bool {
+    fn is_enclosed(&self, coord: &Point2<u64>, cache: &mut HashMap<Point2<u64>, bool>) -> bool {
         if let Some(val) = cache.get(coord) {
             return *val;
         }
@@ -248,7 +199,7 @@ impl Raycaster {
         }
 
         let mut result_from_neighbors: Option<bool> = None;
-        for neighbor in coord.neighbors() {
+        for neighbor in coord.neighbors4() {
             if !self.is_on_border(&neighbor)
                 && let Some(val) = cache.get(&neighbor)
             {
@@ -263,18 +214,18 @@ impl Raycaster {
 
         let direction = self.bounds.closest_edge(coord);
 
-        let ray: Vec<Coordinate> = match direction {
+        let ray: Vec<Point2<u64>> = match direction {
             Direction::Left => (self.bounds.x_min..=coord.x)
-                .map(|x| Coordinate { x, y: coord.y })
+                .map(|x| Point2 { x, y: coord.y })
                 .collect(),
             Direction::Right => (coord.x..=self.bounds.x_max)
-                .map(|x| Coordinate { x, y: coord.y })
+                .map(|x| Point2 { x, y: coord.y })
                 .collect(),
             Direction::Up => (self.bounds.y_min..=coord.y)
-                .map(|y| Coordinate { x: coord.x, y })
+                .map(|y| Point2 { x: coord.x, y })
                 .collect(),
             Direction::Down => (coord.y..=self.bounds.y_max)
-                .map(|y| Coordinate { x: coord.x, y })
+                .map(|y| Point2 { x: coord.x, y })
                 .collect(),
         };
 
@@ -353,14 +304,16 @@ impl Raycaster {
     }
 }
 
-struct BoxBorderIter {
+/// Walks the border of the rectangle spanned by two corners, visiting each
+/// tile once.
+pub struct BoxBorderIter {
     bounds: Bounds,
     side: Direction,
-    current: Coordinate,
+    current: Point2<u64>,
 }
 
 impl BoxBorderIter {
-    fn new(a: &Coordinate, b: &Coordinate) -> Self {
+    pub fn new(a: &Point2<u64>, b: &Point2<u64>) -> Self {
         let x_min = a.x.min(b.x);
         let x_max = a.x.max(b.x);
         let y_min = a.y.min(b.y);
@@ -374,13 +327,13 @@ impl BoxBorderIter {
                 y_max,
             },
             side: Direction::Left,
-            current: Coordinate { x: x_min, y: y_min },
+            current: Point2 { x: x_min, y: y_min },
         }
     }
 }
 
 impl Iterator for BoxBorderIter {
-    type Item = Coordinate;
+    type Item = Point2<u64>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let result = self.current;
@@ -436,7 +389,7 @@ impl CoordinateCompressor {
         }
     }
 
-    fn compress_all(&mut self, tiles: &[Coordinate]) -> Vec<Coordinate> {
+    fn compress_all(&mut self, tiles: &[Point2<u64>]) -> Vec<Point2<u64>> {
         let xs = {
             let mut v: Vec<u64> = tiles.iter().map(|c| c.x).collect();
             v.sort_unstable();
@@ -458,64 +411,38 @@ impl CoordinateCompressor {
 
         tiles
             .iter()
-            .map(|c| Coordinate {
+            .map(|c| Point2 {
                 x: *x_comp_map.get(&c.x).unwrap() as u64,
                 y: *y_comp_map.get(&c.y).unwrap() as u64,
             })
             .collect()
     }
 
-    fn decompress(&self, coord: &Coordinate) -> Coordinate {
-        Coordinate {
+    fn decompress(&self, coord: &Point2<u64>) -> Point2<u64> {
+        Point2 {
             x: *self.x_decomp_map.get(&coord.x).unwrap(),
             y: *self.y_decomp_map.get(&coord.y).unwrap(),
         }
     }
 }
 
-pub fn part1(input: &str) -> String {
-    let tiles: Result<Vec<Coordinate>, ParseCoordinateError> = input
-        .lines()
-        .map(|line| line.parse::<Coordinate>())
-        .collect();
-    let tiles = match tiles {
-        Ok(t) => t,
-        Err(_) => return "Error parsing input".into(),
-    };
-
-    let mut area_entries: Vec<AreaEntry> = Vec::new();
-    for (i, a) in tiles.iter().enumerate() {
-        for (j, b) in tiles.iter().enumerate().skip(i + 1) {
-            let dist = a.rectangle_area(b);
-            area_entries.push(AreaEntry {
-                tile_ids: (i, j),
-                area: dist,
-            });
-        }
+/// Finds the largest rectangle inside the loop by trying rectangles from the
+/// largest down, casting rays from the tiles on the border of each to see if
+/// they are enclosed.
+fn raycast(tiles: &[Point2<u64>], _params: &Params) -> Result<Answer, SolveError> {
+    if tiles.len() < 2 {
+        return Err(SolveError::Unsolvable("fewer than two red tiles".into()));
     }
 
-    area_entries.iter().max().unwrap().area.to_string()
-}
-
-pub fn part2(input: &str) -> String {
-    let tiles: Result<Vec<Coordinate>, ParseCoordinateError> = input
-        .lines()
-        .map(|line| line.parse::<Coordinate>())
-        .collect();
-    let tiles = match tiles {
-        Ok(t) => t,
-        Err(_) => return "Error parsing input".into(),
-    };
-
     let mut compressor = CoordinateCompressor::new();
-    let tiles = compressor.compress_all(&tiles);
+    let tiles = compressor.compress_all(tiles);
 
     let mut area_entries: Vec<AreaEntry> = Vec::new();
     for (i, a) in tiles.iter().enumerate() {
         for (j, b) in tiles.iter().enumerate().skip(i + 1) {
             let decomp_a = compressor.decompress(a);
             let decomp_b = compressor.decompress(b);
-            let area = decomp_a.rectangle_area(&decomp_b);
+            let area = rectangle_area(&decomp_a, &decomp_b);
             area_entries.push(AreaEntry {
                 tile_ids: (i, j),
                 area,
@@ -525,7 +452,7 @@ pub fn part2(input: &str) -> String {
 
     let raycaster = Raycaster::new(tiles);
 
-    let mut cache: HashMap<Coordinate, bool> = HashMap::new();
+    let mut cache: HashMap<Point2<u64>, bool> = HashMap::new();
 
     let mut max_heap = BinaryHeap::from(area_entries);
     loop {
@@ -536,36 +463,192 @@ pub fn part2(input: &str) -> String {
             )
             .all(|coord| raycaster.is_enclosed(&coord, &mut cache));
             if is_valid_rectangle {
-                return entry.area.to_string();
+                return Ok(entry.area.into());
             }
         } else {
-            unreachable!("No rectangle found");
+            return Err(SolveError::Unsolvable("no rectangle fits inside the loop".into()));
         }
     }
 }
 
+/// Finds the largest rectangle inside the loop by marking the tiles outside it
+/// on a compressed grid and counting those in each rectangle with a 2D prefix
+/// sum.
+fn prefix_sum(tiles: &[Point2<u64>], _params: &Params) -> Result<Answer, SolveError> {
+    if tiles.len() < 2 {
+        return Err(SolveError::Unsolvable("fewer than two red tiles".into()));
+    }
+
+    let axis = |coordinate: fn(&Point2<u64>) -> u64| {
+        let mut v: Vec<u64> = tiles.iter().map(coordinate).collect();
+        v.sort_unstable();
+        v.dedup();
+        v
+    };
+    let xs = axis(|c| c.x);
+    let ys = axis(|c| c.y);
+
+    // Every distinct coordinate gets an odd index and the gap after it the
+    // even one, with a ring of gaps around the whole loop.
+    let index = |values: &[u64], v: u64| 2 * values.binary_search(&v).unwrap() + 1;
+    let span = |values: &[u64], a: u64, b: u64| {
+        let (a, b) = (index(values, a), index(values, b));
+        (a.min(b), a.max(b))
+    };
+    let (width, height) = (2 * xs.len() + 1, 2 * ys.len() + 1);
+
+    let mut border = vec![vec![false; width]; height];
+    for (a, b) in tiles.iter().zip(tiles.iter().cycle().skip(1)) {
+        let (x0, x1) = span(&xs, a.x, b.x);
+        let (y0, y1) = span(&ys, a.y, b.y);
+        for row in &mut border[y0..=y1] {
+            row[x0..=x1].fill(true);
+        }
+    }
+
+    let mut outside = vec![vec![false; width]; height];
+    outside[0][0] = true;
+    let mut stack = vec![(0_usize, 0_usize)];
+    while let Some((x, y)) = stack.pop() {
+        for (nx, ny) in [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ] {
+            if nx < width && ny < height && !border[ny][nx] && !outside[ny][nx] {
+                outside[ny][nx] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+
+    // prefix[y][x] counts the outside cells above and left of (x, y).
+    let mut prefix = vec![vec![0u32; width + 1]; height + 1];
+    for y in 0..height {
+        for x in 0..width {
+            prefix[y + 1][x + 1] =
+                prefix[y][x + 1] + prefix[y + 1][x] - prefix[y][x] + outside[y][x] as u32;
+        }
+    }
+
+    let mut best = None;
+    for (i, a) in tiles.iter().enumerate() {
+        for b in &tiles[i + 1..] {
+            let area = rectangle_area(a, b);
+            if best.is_some_and(|best| best >= area) {
+                continue;
+            }
+            let (x0, x1) = span(&xs, a.x, b.x);
+            let (y0, y1) = span(&ys, a.y, b.y);
+            let outside_cells =
+                prefix[y1 + 1][x1 + 1] + prefix[y0][x0] - prefix[y0][x1 + 1] - prefix[y1 + 1][x0];
+            if outside_cells == 0 {
+                best = Some(area);
+            }
+        }
+    }
+
+    best.map(Answer::from)
+        .ok_or_else(|| SolveError::Unsolvable("no rectangle fits inside the loop".into()))
+}
+
+const EXAMPLE: &str = r#"7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3"#;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<Point2<u64>>;
+
+    const INFO: Info = Info {
+        title: "Movie Theater",
+        description: "Find the largest rectangle with red tiles in opposite corners, then the largest inside their loop.",
+        tags: &[Tag::Geometry],
+        status: [Status::Solved, Status::Solved],
+    };
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("50"),
+        part2: Some("24"),
+        params: &[],
+    }];
+
+    const VARIANTS: &'static [Variant] = &[
+        Variant {
+            part: 2,
+            name: "raycast",
+            solve: |tiles, params| raycast(downcast::<Day09>(tiles), params),
+        },
+        Variant {
+            part: 2,
+            name: "prefix_sum",
+            solve: |tiles, params| prefix_sum(downcast::<Day09>(tiles), params),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| Point2::parse(i, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(tiles: &Self::Parsed, _params: &Params) -> Result<Answer, SolveError> {
+        let mut area_entries: Vec<AreaEntry> = Vec::new();
+        for (i, a) in tiles.iter().enumerate() {
+            for (j, b) in tiles.iter().enumerate().skip(i + 1) {
+                let dist = rectangle_area(a, b);
+                area_entries.push(AreaEntry {
+                    tile_ids: (i, j),
+                    area: dist,
+                });
+            }
+        }
+
+        area_entries
+            .iter()
+            .max()
+            .map(|entry| entry.area.into())
+            .ok_or_else(|| SolveError::Unsolvable("fewer than two red tiles".into()))
+    }
+
+    fn part2(tiles: &Self::Parsed, params: &Params) -> Result<Answer, SolveError> {
+        raycast(tiles, params)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::assert_examples;
 
     #[test]
     fn test_box_border_iter() {
-        let a = Coordinate { x: 2, y: 3 };
-        let b = Coordinate { x: 5, y: 6 };
+        let a = Point2 { x: 2, y: 3 };
+        let b = Point2 { x: 5, y: 6 };
         let mut iter = BoxBorderIter::new(&a, &b);
         let expected_coords = vec![
-            Coordinate { x: 2, y: 3 },
-            Coordinate { x: 2, y: 4 },
-            Coordinate { x: 2, y: 5 },
-            Coordinate { x: 2, y: 6 },
-            Coordinate { x: 3, y: 6 },
-            Coordinate { x: 4, y: 6 },
-            Coordinate { x: 5, y: 6 },
-            Coordinate { x: 5, y: 5 },
-            Coordinate { x: 5, y: 4 },
-            Coordinate { x: 5, y: 3 },
-            Coordinate { x: 4, y: 3 },
-            Coordinate { x: 3, y: 3 },
+            Point2 { x: 2, y: 3 },
+            Point2 { x: 2, y: 4 },
+            Point2 { x: 2, y: 5 },
+            Point2 { x: 2, y: 6 },
+            Point2 { x: 3, y: 6 },
+            Point2 { x: 4, y: 6 },
+            Point2 { x: 5, y: 6 },
+            Point2 { x: 5, y: 5 },
+            Point2 { x: 5, y: 4 },
+            Point2 { x: 5, y: 3 },
+            Point2 { x: 4, y: 3 },
+            Point2 { x: 3, y: 3 },
         ];
         for expected in expected_coords {
             let coord = iter.next().unwrap();
@@ -576,27 +659,21 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = r#"7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3"#;
-        assert_eq!(part1(input), "50");
+        assert_examples::<Day09>(1);
     }
 
     #[test]
     fn test_part2() {
-        let input = r#"7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3"#;
-        assert_eq!(part2(input), "24");
+        assert_examples::<Day09>(2);
+    }
+
+    #[test]
+    fn test_too_few_tiles() {
+        let params = Params::of::<Day09>();
+        let unsolvable = Err(SolveError::Unsolvable("fewer than two red tiles".into()));
+        for tiles in [vec![], vec![Point2::new(7, 1)]] {
+            assert_eq!(raycast(&tiles, &params), unsolvable);
+            assert_eq!(prefix_sum(&tiles, &params), unsolvable);
+        }
     }
 }
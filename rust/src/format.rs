@@ -0,0 +1,318 @@
+use crate::answer::Answer;
+use crate::hash::to_hex;
+use crate::runner::{DayOutcome, Run, Timing, render_table};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "Unknown format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// One part of one day, flattened out of a `DayReport` for the structured
+/// formats.
+struct Record {
+    day: u8,
+    part: u8,
+    answer: Option<Answer>,
+    duration_ns: Option<u128>,
+    /// Time spent parsing the day's input, repeated on both of its parts.
+    parse_ns: Option<u128>,
+    status: &'static str,
+    /// Why a failed part has no answer.
+    error: Option<String>,
+    input_hash: Option<String>,
+}
+
+fn records(run: &Run) -> Vec<Record> {
+    let mut records = vec![];
+    for report in &run.reports {
+        let input_hash = report.input_hash.map(to_hex);
+        match &report.outcome {
+            DayOutcome::NotImplemented => {
+                for part in [1, 2] {
+                    records.push(Record {
+                        day: report.day,
+                        part,
+                        answer: None,
+                        duration_ns: None,
+                        parse_ns: None,
+                        status: "not-implemented",
+                        error: None,
+                        input_hash: input_hash.clone(),
+                    });
+                }
+            }
+            DayOutcome::Solved {
+                parse,
+                part1,
+                part2,
+            } => {
+                let parsed = !(part1.cached && part2.cached);
+                for (part, result) in [(1, part1), (2, part2)] {
+                    let (status, error) = match &result.answer {
+                        Ok(_) if result.cached => ("cached", None),
+                        Ok(_) => ("ok", None),
+                        Err(e) => (e.kind(), Some(e.to_string())),
+                    };
+                    records.push(Record {
+                        day: report.day,
+                        part,
+                        answer: result.answer.as_ref().ok().cloned(),
+                        duration_ns: (!result.cached).then_some(result.elapsed.as_nanos()),
+                        parse_ns: parsed.then_some(parse.as_nanos()),
+                        status,
+                        error,
+                        input_hash: input_hash.clone(),
+                    });
+                }
+            }
+        }
+    }
+    records
+}
+
+pub fn render(format: Format, run: &Run) -> String {
+    match format {
+        Format::Text => render_table(run),
+        Format::Json => render_json(&records(run), run.timing),
+        Format::Csv => render_csv(&records(run), run.timing),
+    }
+}
+
+/// Whether the durations were measured serially or under contention.
+fn timing_name(timing: Timing) -> &'static str {
+    match timing {
+        Timing::Serial => "serial",
+        Timing::Parallel { .. } => "parallel",
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Integer answers are written as JSON numbers, even when they are too big
+/// for a double, so that readers with big number support get them exactly.
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(_) | Answer::BigInt(_) => answer.to_string(),
+        Answer::Text(s) => json_string(s),
+    }
+}
+
+fn render_json(records: &[Record], timing: Timing) -> String {
+    let or_null = |value: Option<String>| value.unwrap_or_else(|| "null".into());
+
+    let objects: Vec<String> = records
+        .iter()
+        .map(|r| {
+            format!(
+                r#"  {{"day": {}, "part": {}, "answer": {}, "duration_ns": {}, "parse_ns": {}, "timing": {}, "status": {}, "error": {}, "input_hash": {}}}"#,
+                r.day,
+                r.part,
+                or_null(r.answer.as_ref().map(json_answer)),
+                or_null(r.duration_ns.map(|d| d.to_string())),
+                or_null(r.parse_ns.map(|d| d.to_string())),
+                json_string(timing_name(timing)),
+                json_string(r.status),
+                or_null(r.error.as_deref().map(json_string)),
+                or_null(r.input_hash.as_deref().map(json_string)),
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        "[]\n".into()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_csv(records: &[Record], timing: Timing) -> String {
+    let mut out =
+        String::from("day,part,answer,duration_ns,parse_ns,timing,status,error,input_hash\n");
+    for r in records {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            r.day,
+            r.part,
+            csv_field(&r.answer.as_ref().map(Answer::to_string).unwrap_or_default()),
+            r.duration_ns.map(|d| d.to_string()).unwrap_or_default(),
+            r.parse_ns.map(|d| d.to_string()).unwrap_or_default(),
+            timing_name(timing),
+            r.status,
+            csv_field(r.error.as_deref().unwrap_or("")),
+            r.input_hash.as_deref().unwrap_or(""),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+    use crate::runner::{DayReport, PartError, PartResult};
+    use std::time::Duration;
+
+    fn run(timing: Timing) -> Run {
+        let part = |answer: Answer, nanos| PartResult {
+            answer: Ok(answer),
+            elapsed: Duration::from_nanos(nanos),
+            cached: false,
+        };
+        let timed_out = PartResult {
+            answer: Err(PartError::Timeout(Duration::from_secs(1))),
+            elapsed: Duration::from_secs(1),
+            cached: false,
+        };
+        let line = "x";
+        let unparsed = || PartResult {
+            answer: Err(PartError::Solve(
+                ParseError::new(0, line, line, "a number")
+                    .with_day(3)
+                    .into(),
+            )),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        let reports = vec![
+            DayReport {
+                day: 1,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::from_nanos(700),
+                    part1: part(Answer::Int(3), 1500),
+                    part2: part("a,\"b\"".into(), 20),
+                },
+                input_hash: Some(0xff),
+            },
+            DayReport {
+                day: 2,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::from_nanos(40),
+                    part1: timed_out,
+                    part2: part(Answer::BigInt(1 << 70), 5),
+                },
+                input_hash: Some(0xee),
+            },
+            DayReport {
+                day: 3,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::from_nanos(10),
+                    part1: unparsed(),
+                    part2: unparsed(),
+                },
+                input_hash: Some(0xdd),
+            },
+            DayReport {
+                day: 12,
+                outcome: DayOutcome::NotImplemented,
+                input_hash: None,
+            },
+        ];
+        Run {
+            reports,
+            timing,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = render(Format::Json, &run(Timing::Serial));
+        assert_eq!(
+            json.lines().nth(1),
+            Some(
+                r#"  {"day": 1, "part": 1, "answer": 3, "duration_ns": 1500, "parse_ns": 700, "timing": "serial", "status": "ok", "error": null, "input_hash": "00000000000000ff"},"#
+            )
+        );
+        assert!(json.contains(r#""answer": "a,\"b\"""#));
+        assert!(json.contains(r#""answer": 1180591620717411303424,"#));
+        assert!(json.contains(
+            r#"{"day": 2, "part": 1, "answer": null, "duration_ns": 1000000000, "parse_ns": 40, "timing": "serial", "status": "timeout", "error": "timed out after 1.00s", "input_hash": "00000000000000ee"},"#
+        ));
+        assert!(json.contains(
+            r#"{"day": 12, "part": 2, "answer": null, "duration_ns": null, "parse_ns": null, "timing": "serial", "status": "not-implemented", "error": null, "input_hash": null}"#
+        ));
+        assert!(json.contains(
+            r#"{"day": 3, "part": 2, "answer": null, "duration_ns": 0, "parse_ns": 10, "timing": "serial", "status": "parse-error", "error": "parse error: day 3, line 1, column 1: expected a number, found 'x'", "input_hash": "00000000000000dd"},"#
+        ));
+        let empty = Run {
+            reports: vec![],
+            timing: Timing::Serial,
+            elapsed: Duration::ZERO,
+        };
+        assert_eq!(render(Format::Json, &empty), "[]\n");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let csv = render(Format::Csv, &run(Timing::Parallel { jobs: 2 }));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "day,part,answer,duration_ns,parse_ns,timing,status,error,input_hash"
+        );
+        assert_eq!(lines[1], "1,1,3,1500,700,parallel,ok,,00000000000000ff");
+        assert_eq!(
+            lines[2],
+            r#"1,2,"a,""b""",20,700,parallel,ok,,00000000000000ff"#
+        );
+        assert_eq!(
+            lines[3],
+            "2,1,,1000000000,40,parallel,timeout,timed out after 1.00s,00000000000000ee"
+        );
+        assert_eq!(
+            lines[5],
+            r#"3,1,,0,10,parallel,parse-error,"parse error: day 3, line 1, column 1: expected a number, found 'x'",00000000000000dd"#
+        );
+        assert_eq!(lines[7], "12,1,,,,parallel,not-implemented,,");
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Environment variable pointing at a directory of `dayNN.txt` files that
+/// replaces the bundled `inputs/` directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The profile whose inputs sit directly in the inputs directory. Every
+/// other profile is a subdirectory of it, named after the profile.
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `dayNN.txt` of a profile, from `$AOC_INPUT_DIR` or from `inputs/` next
+    /// to the crate.
+    Profile(String),
+    Path(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl InputSource {
+    /// Interprets the value of `--input`, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::Path(arg.into())
+        }
+    }
+
+    /// The profile whose inputs are read, if the input is not given explicitly.
+    pub fn profile(&self) -> Option<&str> {
+        match self {
+            InputSource::Profile(profile) => Some(profile),
+            _ => None,
+        }
+    }
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Profile(DEFAULT_PROFILE.into())
+    }
+}
+
+/// The directory default inputs are read from. Resolved from the crate root
+/// rather than the working directory so the runner works from anywhere.
+pub fn input_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => dir.into(),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("crate root has a parent directory")
+            .join("inputs"),
+    }
+}
+
+/// The directory holding the inputs and answers of `profile`.
+pub fn profile_dir(profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        input_dir()
+    } else {
+        input_dir().join(profile)
+    }
+}
+
+pub fn input_path(profile: &str, day: u8) -> PathBuf {
+    profile_dir(profile).join(format!("day{:02}.txt", day))
+}
+
+pub fn default_input_path(day: u8) -> PathBuf {
+    input_path(DEFAULT_PROFILE, day)
+}
+
+/// The default profile followed by every other profile in name order.
+pub fn profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = fs::read_dir(input_dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != DEFAULT_PROFILE && !name.starts_with('.'))
+        .collect();
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.into());
+    profiles
+}
+
+pub fn read_input(day: u8, source: &InputSource) -> Result<String, String> {
+    let read_file = |path: PathBuf| {
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read input file {}: {}", path.display(), e))
+    };
+
+    match source {
+        InputSource::Profile(profile) => read_file(input_path(profile, day)),
+        InputSource::Path(path) => read_file(path.clone()),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Could not read input from stdin: {}", e))?;
+            Ok(input)
+        }
+        InputSource::Inline(input) => Ok(input.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("friend/day01.txt"),
+            InputSource::Path("friend/day01.txt".into())
+        );
+    }
+
+    #[test]
+    fn test_read_input() {
+        let source = InputSource::Inline("L68\nR48".into());
+        assert_eq!(read_input(1, &source), Ok("L68\nR48".into()));
+
+        let source = InputSource::Path("does/not/exist.txt".into());
+        let err = read_input(1, &source).unwrap_err();
+        assert!(err.contains("does/not/exist.txt"));
+    }
+
+    #[test]
+    fn test_profile_paths() {
+        assert_eq!(profile_dir(DEFAULT_PROFILE), input_dir());
+        assert_eq!(
+            input_path("friend", 7),
+            input_dir().join("friend").join("day07.txt")
+        );
+        assert_eq!(InputSource::default().profile(), Some(DEFAULT_PROFILE));
+        assert_eq!(InputSource::Stdin.profile(), None);
+        assert_eq!(profiles()[0], DEFAULT_PROFILE);
+    }
+}
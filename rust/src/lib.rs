@@ -0,0 +1,30 @@
+//! Advent of Code 2025 solutions, the registry of days and the runner that
+//! drives them. The `aoc2025` binary is a command line front end to this
+//! library.
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod catalogue;
+pub mod days;
+pub mod format;
+pub mod hash;
+pub mod input;
+pub mod parse;
+pub mod profiles;
+pub mod runner;
+pub mod scaffold;
+pub mod utils;
+pub mod watch;
+
+pub use answer::{Answer, SolveError};
+pub use days::{DAYS, Day, Example, Info, Param, Params, Solution, Status, Tag, Variant, get_day};
+pub use input::InputSource;
+pub use parse::ParseError;
+pub use runner::{
+    CrossCheck, DayOutcome, DayReport, PartError, PartResult, Run, cross_check, run_days,
+    run_examples,
+};
+pub use utils::rollback_union_find::{MergeSequence, RollbackUnionFind};
+pub use utils::union_find::UnionFind;
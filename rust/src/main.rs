@@ -1,52 +1,180 @@
-mod days;
-mod utils;
+mod cli;
 
-use days::get_day;
+use aoc2025::cache::Cache;
+use aoc2025::{answers, catalogue, format, input, profiles, runner, scaffold, watch};
 use std::env;
-use std::fs;
-use std::time::Instant;
+use std::process::ExitCode;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        return Err("Usage: cargo run <day>".into());
-    }
+fn main() -> ExitCode {
+    let command = match cli::parse_command(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
 
-    let day = if let Ok(day) = args[1].parse() {
-        day
-    } else {
-        return Err(format!("Day must be a number, got '{}'", args[1]).into());
+    let result = match command {
+        cli::Command::Run(options) => run(&options),
+        cli::Command::New(day) => new_day(day),
+        cli::Command::List(tag) => {
+            print!("{}", catalogue::render_list(tag));
+            Ok(ExitCode::SUCCESS)
+        }
+        cli::Command::Watch { day, input, args } => {
+            watch::watch(day, &input, &args).map(|()| ExitCode::SUCCESS)
+        }
     };
 
-    if !(1..=25).contains(&day) {
-        return Err("Day must be between 1 and 25".into());
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
     }
+}
 
-    let input_path = format!("../inputs/day{:02}.txt", day);
-    let input_path = fs::canonicalize(input_path)?;
-    let input_path = input_path.as_path();
+fn new_day(day: u8) -> Result<ExitCode, String> {
+    for path in scaffold::new_day(day)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(ExitCode::SUCCESS)
+}
 
-    let input = if let Ok(input) = fs::read_to_string(input_path) {
-        input
+fn run(options: &cli::Options) -> Result<ExitCode, String> {
+    if let Some(config) = options.bench {
+        let mut reports = vec![];
+        for &day in &options.days {
+            reports.extend(runner::bench_day(
+                day,
+                &options.input,
+                &options.params,
+                options.variant.as_deref(),
+                config,
+                options.timeout,
+            )?);
+        }
+        print!("{}", runner::render_bench_table(&reports));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if options.cross_check {
+        let checks = runner::cross_check(
+            &options.days,
+            &options.input,
+            &options.params,
+            options.timeout,
+            options.jobs,
+        )?;
+        print!("{}", runner::render_cross_check_table(&checks));
+        return Ok(if checks.iter().any(|c| c.failed()) {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    if options.example {
+        let results = runner::run_examples(
+            &options.days,
+            options.variant.as_deref(),
+            options.timeout,
+            options.jobs,
+        );
+        print!("{}", runner::render_example_table(&results));
+        return Ok(if results.iter().any(|r| r.failed()) {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    // Verifying answers always solves them again, in case a cached answer
+    // was stored by code that has since been broken in a way the cache key
+    // does not see.
+    let cache = if options.no_cache || options.check || options.all_profiles {
+        Cache::open().refreshing()
     } else {
-        return Err(format!("Could not read file {}", input_path.to_str().unwrap()).into());
+        Cache::open()
     };
 
-    let (part1, part2) = get_day(day);
+    if options.all_profiles {
+        let runs = profiles::run_profiles(
+            &input::profiles(),
+            &options.days,
+            &options.params,
+            options.variant.as_deref(),
+            Some(&cache),
+            options.timeout,
+            options.jobs,
+        )?;
+        print!("{}", profiles::render_profile_table(&runs));
+
+        if options.record {
+            println!();
+            for r in &runs {
+                let path = answers::answers_path(&r.profile);
+                let mut stored = answers::Answers::load(&path)?;
+                let recorded = answers::record(&mut stored, &r.checks);
+                if recorded > 0 {
+                    stored.save(&path)?;
+                }
+                println!("Recorded {} new answers to {}", recorded, path.display());
+            }
+        }
+
+        return Ok(if runs.iter().any(|r| r.failed()) {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    let run = runner::run_days(
+        &options.days,
+        &options.input,
+        &options.params,
+        options.variant.as_deref(),
+        Some(&cache),
+        options.timeout,
+        options.jobs,
+    )?;
 
-    println!("Part 1:");
-    let start = Instant::now();
-    println!("{}", part1(&input));
-    let duration = start.elapsed();
-    println!("Time elapsed: {:?}", duration);
+    print!("{}", format::render(options.format, &run));
+
+    let any_part_failed = run.reports.iter().any(|r| r.failed());
+    if !options.check {
+        return Ok(if any_part_failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    let profile = options
+        .input
+        .profile()
+        .expect("--check only accepts the inputs of a profile");
+    let path = answers::answers_path(profile);
+    let mut stored = answers::Answers::load(&path)?;
+    let checks = answers::check(&stored, &run.reports);
 
     println!();
+    print!("{}", answers::render_checks(&checks));
 
-    println!("Part 2:");
-    let start = Instant::now();
-    println!("{}", part2(&input));
-    let duration = start.elapsed();
-    println!("Time elapsed: {:?}", duration);
+    if options.record {
+        let recorded = answers::record(&mut stored, &checks);
+        if recorded > 0 {
+            stored.save(&path)?;
+        }
+        println!("\nRecorded {} new answers to {}", recorded, path.display());
+    }
 
-    Ok(())
+    let failed = checks.iter().any(|c| c.status.is_failure());
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
 }
@@ -0,0 +1,115 @@
+use std::fmt;
+
+/// Where and why an input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, which knows which day it is parsing.
+    pub day: Option<u8>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending text.
+    pub found: String,
+    /// A description of what should have been there.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Reports `found` on line `index` (0-based, as from `enumerate`).
+    /// `found` must be a slice of `line`, since its column is worked out from
+    /// its position in it. Other text is reported at the start of the line,
+    /// and fails a debug assertion.
+    pub fn new(index: usize, line: &str, found: &str, expected: impl Into<String>) -> Self {
+        let start = line.as_ptr() as usize;
+        let offset = (found.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + found.len() <= line.len())
+            .filter(|&offset| line.is_char_boundary(offset));
+        debug_assert!(offset.is_some(), "{:?} is not a slice of {:?}", found, line);
+        let offset = offset.unwrap_or(0);
+
+        ParseError {
+            day: None,
+            line: index + 1,
+            column: line[..offset].chars().count() + 1,
+            found: found.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Reports an input without a single line where `expected` should have
+    /// been.
+    pub fn empty_input(expected: impl Into<String>) -> Self {
+        let line = "";
+        ParseError::new(0, line, line, expected)
+    }
+
+    pub fn with_day(self, day: u8) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found '{}'", self.found)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = "L68 ±R4x";
+        let e = ParseError::new(2, line, &line[8..9], "a digit");
+        assert_eq!((e.line, e.column), (3, 8));
+        assert_eq!(e.found, "x");
+
+        let e = ParseError::new(0, line, &line[line.len()..], "a digit");
+        assert_eq!(e.column, 9);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not a slice of")]
+    fn test_found_elsewhere() {
+        ParseError::new(0, "L68", "elsewhere", "a digit");
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "R4x";
+        let e = ParseError::new(0, line, &line[..1], "a direction");
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 1: expected a direction, found 'R'"
+        );
+        let e = ParseError::new(4, line, &line[2..], "a digit").with_day(1);
+        assert_eq!(
+            e.to_string(),
+            "day 1, line 5, column 3: expected a digit, found 'x'"
+        );
+        let e = ParseError::empty_input("a number");
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 1: expected a number, found nothing"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Running the days against the inputs of several profiles. Puzzle inputs
+//! differ per account, and a solver that works on one of them can still fail
+//! on another, for example by relying on a property only some inputs have.
+
+use crate::answers::{self, Answers, Check, CheckStatus};
+use crate::cache::Cache;
+use crate::input::{InputSource, input_path};
+use crate::runner::{Overrides, Run, format_table, run_days};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// The days of one profile, checked against the answers of that profile.
+pub struct ProfileRun {
+    pub profile: String,
+    /// Selected days this profile has no input for, which are not run.
+    pub without_input: Vec<u8>,
+    pub run: Run,
+    pub checks: Vec<Check>,
+}
+
+impl ProfileRun {
+    pub fn failed(&self) -> bool {
+        self.checks.iter().any(|c| c.status.is_failure())
+    }
+}
+
+/// Runs `days` on the inputs of each of `profiles` in turn, skipping the days
+/// a profile has no input for.
+pub fn run_profiles(
+    profiles: &[String],
+    days: &[u8],
+    overrides: &Overrides,
+    variant: Option<&str>,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Result<Vec<ProfileRun>, String> {
+    profiles
+        .iter()
+        .map(|profile| {
+            let (with_input, without_input): (Vec<u8>, Vec<u8>) = days
+                .iter()
+                .partition(|&&day| input_path(profile, day).is_file());
+            let source = InputSource::Profile(profile.clone());
+            let run = run_days(
+                &with_input,
+                &source,
+                overrides,
+                variant,
+                cache,
+                timeout,
+                jobs,
+            )?;
+            let stored = Answers::load(&answers::answers_path(profile))?;
+            let checks = answers::check(&stored, &run.reports);
+            Ok(ProfileRun {
+                profile: profile.clone(),
+                without_input,
+                run,
+                checks,
+            })
+        })
+        .collect()
+}
+
+/// Renders the status of every part on every profile, one column per profile,
+/// followed by the profiles each failing part fails on.
+pub fn render_profile_table(runs: &[ProfileRun]) -> String {
+    let parts: BTreeSet<(u8, u8)> = runs
+        .iter()
+        .flat_map(|r| r.checks.iter().map(|c| (c.day, c.part)))
+        .collect();
+
+    let mut header = vec!["Day".to_string(), "Part".to_string()];
+    header.extend(runs.iter().map(|r| r.profile.clone()));
+
+    let mut rows = vec![];
+    let mut failures = String::new();
+    for &(day, part) in &parts {
+        let mut row = vec![day.to_string(), part.to_string()];
+        let mut failing = vec![];
+        for r in runs {
+            let check = r.checks.iter().find(|c| c.day == day && c.part == part);
+            row.push(match check.map(|c| &c.status) {
+                Some(CheckStatus::Pass) => "PASS".into(),
+                Some(CheckStatus::Fail { .. }) => "FAIL".into(),
+                Some(CheckStatus::Missing) => "MISSING".into(),
+                Some(CheckStatus::Error { .. }) => "ERROR".into(),
+                None if r.without_input.contains(&day) => "no input".into(),
+                None => "-".into(),
+            });
+            match check {
+                Some(
+                    c @ Check {
+                        status: CheckStatus::Fail { expected },
+                        ..
+                    },
+                ) => failing.push(format!(
+                    "{} (expected {}, got {})",
+                    r.profile, expected, c.actual
+                )),
+                Some(Check {
+                    status: CheckStatus::Error { error },
+                    ..
+                }) => failing.push(format!("{} ({})", r.profile, error)),
+                _ => {}
+            }
+        }
+        if !failing.is_empty() {
+            failures.push_str(&format!(
+                "Day {:>2} part {} fails on {}\n",
+                day,
+                part,
+                failing.join(", ")
+            ));
+        }
+        rows.push(row);
+    }
+
+    let mut out = format_table(&header, &rows);
+    out.push('\n');
+    if failures.is_empty() {
+        out.push_str("No failures on any profile\n");
+    } else {
+        out.push_str(&failures);
+    }
+    out
+}
@@ -0,0 +1,1345 @@
+use crate::answer::{Answer, SolveError};
+use crate::bench::{BenchConfig, Stats, bench};
+use crate::cache::{Cache, Key};
+use crate::days::{self, Day, Params, Parsed, PartFunction, get_day};
+use crate::hash::fnv1a;
+use crate::input::{InputSource, read_input};
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartError {
+    /// The solution returned an error, or the input could not be parsed so the
+    /// part never ran.
+    Solve(SolveError),
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl PartError {
+    /// A short name for the kind of failure.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PartError::Solve(SolveError::Parse(_)) => "parse-error",
+            PartError::Solve(SolveError::Unsolvable(_)) => "unsolvable",
+            PartError::Solve(SolveError::NotImplemented) => "not-implemented",
+            PartError::Panic(_) => "panic",
+            PartError::Timeout(_) => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for PartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartError::Solve(e) => write!(f, "{}", e),
+            PartError::Panic(msg) => write!(f, "panicked: {}", msg),
+            PartError::Timeout(limit) => write!(f, "timed out after {:.2?}", limit),
+        }
+    }
+}
+
+pub struct PartResult {
+    pub answer: Result<Answer, PartError>,
+    pub elapsed: Duration,
+    /// Whether the answer came from the cache rather than from solving.
+    pub cached: bool,
+}
+
+impl PartResult {
+    /// Whether the part went wrong. A part that is not implemented yet has
+    /// no answer but has not failed either.
+    pub fn failed(&self) -> bool {
+        match &self.answer {
+            Ok(_) => false,
+            Err(e) => !matches!(e, PartError::Solve(SolveError::NotImplemented)),
+        }
+    }
+
+    /// The time the part took, or `cached` if it was not solved.
+    pub fn display_elapsed(&self) -> String {
+        if self.cached {
+            "cached".into()
+        } else {
+            format!("{:.2?}", self.elapsed)
+        }
+    }
+
+    /// The answer, or a short note on why there is none. The full error is
+    /// listed separately so that it cannot be mistaken for an answer.
+    pub fn display_answer(&self) -> String {
+        match &self.answer {
+            Ok(answer) => answer.to_string(),
+            Err(PartError::Solve(SolveError::NotImplemented)) => "not implemented".into(),
+            Err(e) => format!("ERROR ({})", e.kind()),
+        }
+    }
+}
+
+pub enum DayOutcome {
+    NotImplemented,
+    Solved {
+        /// Time spent parsing the input, shared by both parts.
+        parse: Duration,
+        part1: PartResult,
+        part2: PartResult,
+    },
+}
+
+pub struct DayReport {
+    pub day: u8,
+    pub outcome: DayOutcome,
+    /// Hash of the input the day was solved with, if it was read.
+    pub input_hash: Option<u64>,
+}
+
+impl DayReport {
+    pub fn failed(&self) -> bool {
+        match &self.outcome {
+            DayOutcome::NotImplemented => false,
+            DayOutcome::Solved { part1, part2, .. } => part1.failed() || part2.failed(),
+        }
+    }
+}
+
+/// How the parts of a run were scheduled, which decides how much their
+/// timings can be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timing {
+    Serial,
+    /// Parts ran concurrently and competed for the CPU, so their timings are
+    /// inflated compared to a serial run.
+    Parallel {
+        jobs: usize,
+    },
+}
+
+pub struct Run {
+    pub reports: Vec<DayReport>,
+    pub timing: Timing,
+    /// Wall-clock time of the whole run, including reading the inputs.
+    pub elapsed: Duration,
+}
+
+/// What a benchmark row measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part(part) => write!(f, "{}", part),
+        }
+    }
+}
+
+pub struct BenchReport {
+    pub day: u8,
+    pub step: Step,
+    /// The variant that was measured, for parts with more than one.
+    pub variant: Option<&'static str>,
+    /// The answer of a part, or `None` for the parse step, which has none.
+    pub answer: Result<Option<Answer>, PartError>,
+    /// Only measured when the step succeeded.
+    pub stats: Option<Stats>,
+}
+
+pub struct ExampleResult {
+    pub day: u8,
+    /// 1-based position of the example in the day's list.
+    pub example: usize,
+    pub part: u8,
+    pub result: PartResult,
+    /// The answer the puzzle text gives, if it gives one for this part.
+    pub expected: Option<&'static str>,
+}
+
+/// The answer of one variant of a part in a cross-check.
+pub struct CrossCheck {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'static str,
+    pub result: PartResult,
+    /// Whether the answer is the same as that of the first variant.
+    pub agrees: bool,
+}
+
+impl CrossCheck {
+    pub fn failed(&self) -> bool {
+        self.result.failed() || !self.agrees
+    }
+}
+
+impl ExampleResult {
+    /// Whether the part failed or gave an answer other than the expected one.
+    pub fn failed(&self) -> bool {
+        match (&self.result.answer, self.expected) {
+            (Ok(answer), Some(expected)) => answer.to_string() != expected,
+            _ => self.result.failed(),
+        }
+    }
+}
+
+/// Parses a day selection: `all`, a single day (`9`), a range (`1-5`) or a
+/// comma-separated mix of those (`3,7,9`, `1-3,8`).
+pub fn parse_days(arg: &str) -> Result<Vec<u8>, String> {
+    if arg == "all" {
+        return Ok(days::DAYS.to_vec());
+    }
+
+    let parse_day = |s: &str| -> Result<u8, String> {
+        let day: u8 = s
+            .trim()
+            .parse()
+            .map_err(|_| format!("Day must be a number, got '{}'", s))?;
+        if !(1..=25).contains(&day) {
+            return Err("Day must be between 1 and 25".into());
+        }
+        Ok(day)
+    };
+
+    let mut selected = vec![];
+    for item in arg.split(',') {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("Invalid day range '{}'", item));
+                }
+                selected.extend(start..=end);
+            }
+            None => selected.push(parse_day(item)?),
+        }
+    }
+
+    selected.sort_unstable();
+    selected.dedup();
+    Ok(selected)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
+
+/// Runs `f` on a worker thread so that a panic is reported as an error instead
+/// of taking the whole run down. Returns the result with the time `f` took.
+///
+/// A call that exceeds `timeout` is abandoned: its thread keeps running in the
+/// background until it finishes or the process exits.
+fn run_isolated<T: Send + 'static>(
+    name: String,
+    f: impl FnOnce() -> T + Send + 'static,
+    timeout: Option<Duration>,
+) -> (Result<T, PartError>, Duration) {
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            let start = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let _ = tx.send((result.map_err(panic_message), start.elapsed()));
+        })
+        .expect("failed to spawn worker thread");
+
+    let received = match timeout {
+        Some(limit) => rx.recv_timeout(limit).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => PartError::Timeout(limit),
+            mpsc::RecvTimeoutError::Disconnected => {
+                PartError::Panic("worker thread exited without a result".into())
+            }
+        }),
+        None => rx
+            .recv()
+            .map_err(|_| PartError::Panic("worker thread exited without a result".into())),
+    };
+
+    match received {
+        Ok((result, elapsed)) => (result.map_err(PartError::Panic), elapsed),
+        Err(e) => {
+            let elapsed = match e {
+                PartError::Timeout(limit) => limit,
+                _ => Duration::ZERO,
+            };
+            (Err(e), elapsed)
+        }
+    }
+}
+
+/// Parses a day's input in isolation. Both a rejected input and a panic in
+/// the parser are reported as errors, and parse errors are tagged with the day.
+fn run_parse(
+    day: u8,
+    solution: Day,
+    input: &Arc<str>,
+    timeout: Option<Duration>,
+) -> (Result<Parsed, PartError>, Duration) {
+    let input = Arc::clone(input);
+    let (parsed, elapsed) = run_isolated(
+        format!("day{:02}-parse", day),
+        move || (solution.parse)(&input),
+        timeout,
+    );
+    let parsed = parsed.and_then(|p| {
+        p.map_err(|e| match e {
+            SolveError::Parse(e) => PartError::Solve(e.with_day(day).into()),
+            e => PartError::Solve(e),
+        })
+    });
+    (parsed, elapsed)
+}
+
+/// Solves one part in isolation from an already parsed input.
+fn run_part(
+    day: u8,
+    part: u8,
+    f: PartFunction,
+    parsed: &Parsed,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> PartResult {
+    let parsed = Arc::clone(parsed);
+    let params = params.clone();
+    let (answer, elapsed) = run_isolated(
+        format!("day{:02}-part{}", day, part),
+        move || f(&parsed, &params),
+        timeout,
+    );
+    PartResult {
+        answer: answer.and_then(|a| a.map_err(PartError::Solve)),
+        elapsed,
+        cached: false,
+    }
+}
+
+/// Runs `f` on every task on a pool of `jobs` scoped worker threads,
+/// returning the results in task order regardless of which finished first.
+fn run_pool<T: Sync, R: Send>(tasks: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(tasks.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(task) = tasks.get(i) else {
+                        break;
+                    };
+                    let result = f(task);
+                    results.lock().unwrap()[i] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every task was run"))
+        .collect()
+}
+
+/// Runs `f` on every task on a pool of `jobs` scoped worker threads. `f`
+/// returns the tasks that finishing its task made ready, which are run before
+/// the ones still waiting, so that no task waits for more than it depends on.
+fn run_queue<T: Send>(tasks: Vec<T>, jobs: usize, f: impl Fn(T) -> Vec<T> + Sync) {
+    struct Queue<T> {
+        waiting: VecDeque<T>,
+        running: usize,
+    }
+
+    /// Marks a task as finished even if `f` panics, so that the other
+    /// workers stop waiting for the tasks it might have made ready.
+    struct Finished<'a, T> {
+        queue: &'a Mutex<Queue<T>>,
+        ready: &'a Condvar,
+    }
+
+    impl<T> Drop for Finished<'_, T> {
+        fn drop(&mut self) {
+            let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+            queue.running -= 1;
+            self.ready.notify_all();
+        }
+    }
+
+    let queue = Mutex::new(Queue {
+        waiting: tasks.into(),
+        running: 0,
+    });
+    let ready = Condvar::new();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| {
+                loop {
+                    let task = {
+                        let mut queue = queue.lock().unwrap();
+                        loop {
+                            if let Some(task) = queue.waiting.pop_front() {
+                                queue.running += 1;
+                                break task;
+                            }
+                            if queue.running == 0 {
+                                return;
+                            }
+                            queue = ready.wait(queue).unwrap();
+                        }
+                    };
+                    let finished = Finished {
+                        queue: &queue,
+                        ready: &ready,
+                    };
+                    let next = f(task);
+                    let mut waiting = queue.lock().unwrap();
+                    for task in next.into_iter().rev() {
+                        waiting.waiting.push_front(task);
+                    }
+                    drop(waiting);
+                    drop(finished);
+                }
+            });
+        }
+    });
+}
+
+struct DayTask {
+    day: u8,
+    solution: Day,
+    input: Arc<str>,
+    params: Params,
+    keys: [Key; 2],
+    cached: [Option<Answer>; 2],
+}
+
+struct PartTask {
+    day: u8,
+    part: u8,
+    f: PartFunction,
+    parsed: Parsed,
+    params: Params,
+}
+
+/// The names and values of `--param` overrides.
+pub type Overrides = [(String, usize)];
+
+fn params_for(solution: &Day, overrides: &Overrides) -> Params {
+    solution.params(
+        overrides
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value)),
+    )
+}
+
+/// Work for the pool of `solve_days`: parsing the input of the day at an
+/// index, or solving one of its parts from the parsed input.
+enum Work {
+    Parse(usize),
+    Part(usize, u8, Parsed),
+}
+
+/// Solves the parts of `tasks` that are not cached, running up to `jobs`
+/// parses or parts at the same time. A day's parts are queued as soon as its
+/// input is parsed, so they can run alongside each other and other days.
+fn solve_days(
+    tasks: &[DayTask],
+    variant: Option<&str>,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Vec<DayOutcome> {
+    let solved: Vec<Mutex<(Duration, [Option<PartResult>; 2])>> = tasks
+        .iter()
+        .map(|task| {
+            let parts = task.cached.each_ref().map(|answer| {
+                answer.as_ref().map(|answer| PartResult {
+                    answer: Ok(answer.clone()),
+                    elapsed: Duration::ZERO,
+                    cached: true,
+                })
+            });
+            Mutex::new((Duration::ZERO, parts))
+        })
+        .collect();
+    let unsolved = |i: usize| {
+        [1, 2]
+            .into_iter()
+            .filter(move |&part| tasks[i].cached[part as usize - 1].is_none())
+    };
+
+    // Days with both answers cached need not even be parsed.
+    let parses = (0..tasks.len())
+        .filter(|&i| unsolved(i).next().is_some())
+        .map(Work::Parse)
+        .collect();
+    run_queue(parses, jobs, |step| match step {
+        Work::Parse(i) => {
+            let task = &tasks[i];
+            let (parsed, parse) = run_parse(task.day, task.solution, &task.input, timeout);
+            let mut solved = solved[i].lock().unwrap();
+            solved.0 = parse;
+            match parsed {
+                Ok(parsed) => unsolved(i)
+                    .map(|part| Work::Part(i, part, Arc::clone(&parsed)))
+                    .collect(),
+                Err(e) => {
+                    for part in unsolved(i) {
+                        solved.1[part as usize - 1] = Some(PartResult {
+                            answer: Err(e.clone()),
+                            elapsed: Duration::ZERO,
+                            cached: false,
+                        });
+                    }
+                    vec![]
+                }
+            }
+        }
+        Work::Part(i, part, parsed) => {
+            let task = &tasks[i];
+            let result = run_part(
+                task.day,
+                part,
+                task.solution.variant(part, variant),
+                &parsed,
+                &task.params,
+                timeout,
+            );
+            if let (Some(cache), Ok(answer)) = (cache, &result.answer) {
+                cache.put(&task.keys[part as usize - 1], answer);
+            }
+            solved[i].lock().unwrap().1[part as usize - 1] = Some(result);
+            vec![]
+        }
+    });
+
+    solved
+        .into_iter()
+        .map(|solved| {
+            let (parse, parts) = solved.into_inner().unwrap();
+            let [part1, part2] = parts.map(|part| part.expect("every part was solved"));
+            DayOutcome::Solved {
+                parse,
+                part1,
+                part2,
+            }
+        })
+        .collect()
+}
+
+/// Solves every selected day, running up to `jobs` parses or parts at the same
+/// time. Inputs are all read up front, so a missing input fails the run before
+/// any solving starts. Every input is parsed once and shared by both parts,
+/// which see the day's parameters with `overrides` applied. Parts that have a
+/// variant called `variant` are solved with it. Answers found in `cache` are
+/// not solved again, and new answers are stored in it.
+pub fn run_days(
+    days: &[u8],
+    source: &InputSource,
+    overrides: &Overrides,
+    variant: Option<&str>,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Result<Run, String> {
+    let start = Instant::now();
+
+    let mut input_hashes = Vec::with_capacity(days.len());
+    let mut day_tasks = vec![];
+    for &day in days {
+        let Some(solution) = get_day(day) else {
+            input_hashes.push(None);
+            continue;
+        };
+        let input: Arc<str> = read_input(day, source)?.into();
+        let input_hash = fnv1a(input.as_bytes());
+        input_hashes.push(Some(input_hash));
+        let params = params_for(&solution, overrides);
+        let keys = [1, 2].map(|part| Key {
+            day,
+            part,
+            input_hash,
+            source_hash: fnv1a(solution.source.as_bytes()),
+            shared_hash: days::SHARED_SOURCE_HASH,
+            params: params.to_string(),
+            variant: solution.variant_name(part, variant),
+        });
+        let cached = keys
+            .each_ref()
+            .map(|key| cache.and_then(|cache| cache.get(key)));
+        day_tasks.push(DayTask {
+            day,
+            solution,
+            input,
+            params,
+            keys,
+            cached,
+        });
+    }
+
+    let mut outcomes = solve_days(&day_tasks, variant, cache, timeout, jobs).into_iter();
+
+    let reports = days
+        .iter()
+        .zip(input_hashes)
+        .map(|(&day, input_hash)| DayReport {
+            day,
+            outcome: match input_hash {
+                Some(_) => outcomes.next().expect("day was solved"),
+                None => DayOutcome::NotImplemented,
+            },
+            input_hash,
+        })
+        .collect();
+
+    Ok(Run {
+        reports,
+        timing: if jobs > 1 {
+            Timing::Parallel { jobs }
+        } else {
+            Timing::Serial
+        },
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Benchmarks parsing and both parts of a day. Days that are not implemented
+/// have nothing to measure and yield no reports. Each step is first run once
+/// in isolation, and steps that fail are reported without statistics. The
+/// parts are measured on a single parse, so their timings exclude parsing.
+/// Every variant of a part is measured, unless `variant` picks one.
+pub fn bench_day(
+    day: u8,
+    source: &InputSource,
+    overrides: &Overrides,
+    variant: Option<&str>,
+    config: BenchConfig,
+    timeout: Option<Duration>,
+) -> Result<Vec<BenchReport>, String> {
+    let Some(solution) = get_day(day) else {
+        return Ok(vec![]);
+    };
+
+    let input: Arc<str> = read_input(day, source)?.into();
+    let params = params_for(&solution, overrides);
+    let (parsed, _) = run_parse(day, solution, &input, timeout);
+    let mut reports = vec![BenchReport {
+        day,
+        step: Step::Parse,
+        variant: None,
+        answer: parsed.as_ref().map(|_| None).map_err(Clone::clone),
+        stats: parsed
+            .is_ok()
+            .then(|| bench(config, || (solution.parse)(black_box(&input)))),
+    }];
+
+    let mut steps = vec![];
+    for part in [1, 2] {
+        let variants = solution.variants(part);
+        match variant.and_then(|name| variants.iter().find(|v| v.name == name)) {
+            Some(v) => steps.push((part, Some(v.name), v.solve)),
+            None if variant.is_some() || variants.len() == 1 => {
+                steps.push((part, None, solution.part(part)))
+            }
+            None => steps.extend(variants.iter().map(|v| (part, Some(v.name), v.solve))),
+        }
+    }
+    for (part, name, f) in steps {
+        let (answer, stats) = match &parsed {
+            Ok(parsed) => {
+                let answer = run_part(day, part, f, parsed, &params, timeout).answer;
+                let stats = answer
+                    .is_ok()
+                    .then(|| bench(config, || f(black_box(parsed), &params)));
+                (answer.map(Some), stats)
+            }
+            Err(e) => (Err(e.clone()), None),
+        };
+        reports.push(BenchReport {
+            day,
+            step: Step::Part(part),
+            variant: name,
+            answer,
+            stats,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Solves the examples of every selected day with the parameters each example
+/// sets, running up to `jobs` examples at the same time. Parts that have a
+/// variant called `variant` are solved with it.
+pub fn run_examples(
+    days: &[u8],
+    variant: Option<&str>,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Vec<ExampleResult> {
+    let tasks: Vec<(u8, Day, usize)> = days
+        .iter()
+        .filter_map(|&day| Some((day, get_day(day)?)))
+        .flat_map(|(day, solution)| (0..solution.examples.len()).map(move |i| (day, solution, i)))
+        .collect();
+
+    let results = run_pool(&tasks, jobs, |&(day, solution, i)| {
+        let example = &solution.examples[i];
+        let params = solution.params(example.params.iter().copied());
+        let (parsed, _) = run_parse(day, solution, &example.input.into(), timeout);
+        [1, 2].map(|part| ExampleResult {
+            day,
+            example: i + 1,
+            part,
+            result: match &parsed {
+                Ok(parsed) => run_part(
+                    day,
+                    part,
+                    solution.variant(part, variant),
+                    parsed,
+                    &params,
+                    timeout,
+                ),
+                Err(e) => PartResult {
+                    answer: Err(e.clone()),
+                    elapsed: Duration::ZERO,
+                    cached: false,
+                },
+            },
+            expected: example.expected(part),
+        })
+    });
+
+    results.into_iter().flatten().collect()
+}
+
+/// Solves every part of the selected days with each of its variants, running
+/// up to `jobs` of them at the same time, so that their answers can be
+/// compared.
+pub fn cross_check(
+    days: &[u8],
+    source: &InputSource,
+    overrides: &Overrides,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Result<Vec<CrossCheck>, String> {
+    let mut tasks = vec![];
+    let mut skipped = vec![];
+    for &day in days {
+        let Some(solution) = get_day(day) else {
+            continue;
+        };
+        let input: Arc<str> = read_input(day, source)?.into();
+        let params = params_for(&solution, overrides);
+        let (parsed, _) = run_parse(day, solution, &input, timeout);
+        for part in [1, 2] {
+            for variant in solution.variants(part) {
+                match &parsed {
+                    Ok(parsed) => tasks.push((
+                        variant.name,
+                        PartTask {
+                            day,
+                            part,
+                            f: variant.solve,
+                            parsed: Arc::clone(parsed),
+                            params: params.clone(),
+                        },
+                    )),
+                    Err(e) => skipped.push((day, part, variant.name, e.clone())),
+                }
+            }
+        }
+    }
+
+    let results = run_pool(&tasks, jobs, |(_, task)| {
+        run_part(
+            task.day,
+            task.part,
+            task.f,
+            &task.parsed,
+            &task.params,
+            timeout,
+        )
+    });
+
+    let mut checks: Vec<CrossCheck> = tasks
+        .iter()
+        .zip(results)
+        .map(|((variant, task), result)| CrossCheck {
+            day: task.day,
+            part: task.part,
+            variant,
+            result,
+            agrees: true,
+        })
+        .chain(
+            skipped
+                .into_iter()
+                .map(|(day, part, variant, e)| CrossCheck {
+                    day,
+                    part,
+                    variant,
+                    result: PartResult {
+                        answer: Err(e),
+                        elapsed: Duration::ZERO,
+                        cached: false,
+                    },
+                    agrees: true,
+                }),
+        )
+        .collect();
+    checks.sort_by_key(|check| (check.day, check.part));
+
+    for group in checks.chunk_by_mut(|a, b| (a.day, a.part) == (b.day, b.part)) {
+        let (first, rest) = group.split_first_mut().expect("groups are not empty");
+        for check in rest {
+            check.agrees =
+                first.result.answer.is_ok() && check.result.answer == first.result.answer;
+        }
+    }
+
+    Ok(checks)
+}
+
+/// Renders the reports as a table with one row per day followed by the total
+/// time spent parsing and solving.
+pub fn render_table(run: &Run) -> String {
+    let reports = &run.reports;
+    let header = ["Day", "Parse", "Part 1", "Time", "Part 2", "Time"].map(String::from);
+
+    let rows: Vec<[String; 6]> = reports
+        .iter()
+        .map(|report| match &report.outcome {
+            DayOutcome::NotImplemented => [
+                report.day.to_string(),
+                "-".into(),
+                "not implemented".into(),
+                "-".into(),
+                "not implemented".into(),
+                "-".into(),
+            ],
+            DayOutcome::Solved {
+                parse,
+                part1,
+                part2,
+            } => [
+                report.day.to_string(),
+                if part1.cached && part2.cached {
+                    "cached".into()
+                } else {
+                    format!("{:.2?}", parse)
+                },
+                part1.display_answer(),
+                part1.display_elapsed(),
+                part2.display_answer(),
+                part2.display_elapsed(),
+            ],
+        })
+        .collect();
+
+    let mut out = format_table(&header, &rows);
+
+    out.push_str(&render_errors(reports.iter().flat_map(|report| {
+        let parts = match &report.outcome {
+            DayOutcome::NotImplemented => vec![],
+            DayOutcome::Solved { part1, part2, .. } => vec![(1, part1), (2, part2)],
+        };
+        parts
+            .into_iter()
+            .map(|(part, result)| (format!("Day {:>2} part {}", report.day, part), result))
+    })));
+
+    let total: Duration = reports
+        .iter()
+        .map(|report| match &report.outcome {
+            DayOutcome::NotImplemented => Duration::ZERO,
+            DayOutcome::Solved {
+                parse,
+                part1,
+                part2,
+            } => *parse + part1.elapsed + part2.elapsed,
+        })
+        .sum();
+    out.push_str(&format!("\nTotal time: {:.2?}\n", total));
+    if let Timing::Parallel { jobs } = run.timing {
+        out.push_str(&format!(
+            "Wall-clock time: {:.2?} ({} jobs, part timings are contended)\n",
+            run.elapsed, jobs
+        ));
+    }
+
+    out
+}
+
+pub fn render_bench_table(reports: &[BenchReport]) -> String {
+    let header = [
+        "Day", "Step", "Variant", "Answer", "Iters", "Min", "Median", "Mean", "p95", "Stddev",
+        "Speedup",
+    ]
+    .map(String::from);
+
+    // Variants of a part are compared against the slowest of them.
+    let slowest = |report: &BenchReport| {
+        reports
+            .iter()
+            .filter(|other| (other.day, other.step) == (report.day, report.step))
+            .filter_map(|other| Some(other.stats.as_ref()?.median))
+            .max()
+    };
+
+    let rows: Vec<[String; 11]> = reports
+        .iter()
+        .map(|report| {
+            let speedup = match (report.variant, &report.stats, slowest(report)) {
+                (Some(_), Some(stats), Some(slowest)) if !stats.median.is_zero() => {
+                    format!("{:.2}x", slowest.as_secs_f64() / stats.median.as_secs_f64())
+                }
+                _ => "-".into(),
+            };
+            let answer = match &report.answer {
+                Ok(Some(answer)) => answer.to_string(),
+                Ok(None) => "-".into(),
+                Err(e) => e.to_string(),
+            };
+            let columns = match &report.stats {
+                Some(stats) => [
+                    stats.iterations.to_string(),
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.mean),
+                    format!("{:.2?}", stats.p95),
+                    format!("{:.2?}", stats.stddev),
+                ],
+                None => ["-"; 6].map(String::from),
+            };
+            let [iters, min, median, mean, p95, stddev] = columns;
+            [
+                report.day.to_string(),
+                report.step.to_string(),
+                report.variant.unwrap_or("-").to_string(),
+                answer,
+                iters,
+                min,
+                median,
+                mean,
+                p95,
+                stddev,
+                speedup,
+            ]
+        })
+        .collect();
+
+    format_table(&header, &rows)
+}
+
+/// Lists the full errors of the failed parts, each labelled with where it
+/// happened, or nothing if no part failed.
+fn render_errors<'a>(parts: impl IntoIterator<Item = (String, &'a PartResult)>) -> String {
+    let mut errors = String::new();
+    for (label, result) in parts {
+        if let Err(e) = &result.answer
+            && result.failed()
+        {
+            errors.push_str(&format!("{}: {}\n", label, e));
+        }
+    }
+    if errors.is_empty() {
+        errors
+    } else {
+        format!("\nErrors:\n{}", errors)
+    }
+}
+
+/// Renders one row per part of every example, with its expected answer and
+/// whether it was matched.
+pub fn render_example_table(results: &[ExampleResult]) -> String {
+    let header = [
+        "Day", "Example", "Part", "Answer", "Expected", "Time", "Status",
+    ]
+    .map(String::from);
+
+    let rows: Vec<[String; 7]> = results
+        .iter()
+        .map(|r| {
+            let status = if r.result.failed() {
+                "ERROR"
+            } else if r.result.answer.is_err() || r.expected.is_none() {
+                "-"
+            } else if r.failed() {
+                "FAIL"
+            } else {
+                "PASS"
+            };
+            [
+                r.day.to_string(),
+                r.example.to_string(),
+                r.part.to_string(),
+                r.result.display_answer(),
+                r.expected.unwrap_or("-").to_string(),
+                format!("{:.2?}", r.result.elapsed),
+                status.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut out = format_table(&header, &rows);
+    out.push_str(&render_errors(results.iter().map(|r| {
+        (
+            format!("Day {:>2} example {} part {}", r.day, r.example, r.part),
+            &r.result,
+        )
+    })));
+    out
+}
+
+/// Renders a cross-check with one row per variant of each part.
+pub fn render_cross_check_table(checks: &[CrossCheck]) -> String {
+    let header = ["Day", "Part", "Variant", "Answer", "Time", "Status"].map(String::from);
+
+    let rows: Vec<[String; 6]> = checks
+        .iter()
+        .map(|c| {
+            let status = if c.result.failed() {
+                "ERROR"
+            } else if c.result.answer.is_err() {
+                "-"
+            } else if !c.agrees {
+                "MISMATCH"
+            } else {
+                "OK"
+            };
+            [
+                c.day.to_string(),
+                c.part.to_string(),
+                c.variant.to_string(),
+                c.result.display_answer(),
+                format!("{:.2?}", c.result.elapsed),
+                status.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut out = format_table(&header, &rows);
+    out.push_str(&render_errors(checks.iter().map(|c| {
+        (
+            format!("Day {:>2} part {} {}", c.day, c.part, c.variant),
+            &c.result,
+        )
+    })));
+    out
+}
+
+/// Lays out left-aligned columns separated by `|`, with a rule under the header.
+pub fn format_table<R: AsRef<[String]>>(header: &[String], rows: &[R]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.as_ref()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = String::new();
+    out.push_str(&format_row(header));
+    out.push('\n');
+    out.push_str(
+        &widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    out.push('\n');
+    for row in rows {
+        out.push_str(&format_row(row.as_ref()));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("9"), Ok(vec![9]));
+        assert_eq!(parse_days("1-5"), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(parse_days("3,7,9"), Ok(vec![3, 7, 9]));
+        assert_eq!(parse_days("7,1-3,2"), Ok(vec![1, 2, 3, 7]));
+        assert_eq!(parse_days("all"), Ok(days::DAYS.to_vec()));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("5-3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    fn parsed(input: &str) -> Parsed {
+        Arc::new(input.to_string())
+    }
+
+    fn count_lines(parsed: &Parsed, _: &Params) -> Result<Answer, SolveError> {
+        let input: &String = parsed.downcast_ref().unwrap();
+        Ok(input.lines().count().into())
+    }
+
+    fn panicking(_: &Parsed, _: &Params) -> Result<Answer, SolveError> {
+        panic!("bad input")
+    }
+
+    fn looping(_: &Parsed, _: &Params) -> Result<Answer, SolveError> {
+        loop {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_run_part() {
+        let input = parsed("L68\nR48");
+        let params = Params::new(&[]);
+        let result = run_part(1, 1, count_lines, &input, &params, None);
+        assert_eq!(result.answer, Ok(Answer::Int(2)));
+
+        let result = run_part(1, 1, panicking, &input, &params, None);
+        assert_eq!(result.answer, Err(PartError::Panic("bad input".into())));
+
+        let limit = Duration::from_millis(50);
+        let result = run_part(1, 1, looping, &input, &params, Some(limit));
+        assert_eq!(result.answer, Err(PartError::Timeout(limit)));
+    }
+
+    #[test]
+    fn test_run_parse() {
+        let solution = Day {
+            parse: |input| match input {
+                "" => Err(ParseError::empty_input("a line").into()),
+                "!" => panic!("unexpected '!'"),
+                _ => Ok(parsed(input)),
+            },
+            part1: count_lines,
+            part2: count_lines,
+            ..get_day(1).unwrap()
+        };
+        let (result, _) = run_parse(1, solution, &"a\nb".into(), None);
+        assert_eq!(
+            count_lines(&result.unwrap(), &Params::new(&[])),
+            Ok(Answer::Int(2))
+        );
+
+        let (result, _) = run_parse(1, solution, &"".into(), None);
+        assert_eq!(
+            result.err(),
+            Some(PartError::Solve(
+                ParseError::empty_input("a line").with_day(1).into()
+            ))
+        );
+
+        let (result, _) = run_parse(1, solution, &"!".into(), None);
+        assert_eq!(
+            result.err(),
+            Some(PartError::Panic("unexpected '!'".into()))
+        );
+    }
+
+    #[test]
+    fn test_run_pool() {
+        let input = parsed("a\nb\nc");
+        let tasks: Vec<PartTask> = (1..=8)
+            .map(|day| PartTask {
+                day,
+                part: 1,
+                f: if day % 3 == 0 { panicking } else { count_lines },
+                parsed: Arc::clone(&input),
+                params: Params::new(&[]),
+            })
+            .collect();
+
+        for jobs in [1, 3, 16] {
+            let results = run_pool(&tasks, jobs, |task| {
+                run_part(
+                    task.day,
+                    task.part,
+                    task.f,
+                    &task.parsed,
+                    &task.params,
+                    None,
+                )
+            });
+            let answers: Vec<_> = results.iter().map(|r| r.answer.is_ok()).collect();
+            assert_eq!(
+                answers,
+                [true, true, false, true, true, false, true, true],
+                "jobs = {}",
+                jobs
+            );
+        }
+    }
+
+    #[test]
+    fn test_parts_overlap() {
+        // Each part blocks until the other one is running too, so both only
+        // finish within the timeout if the pool runs a day's parts together.
+        static BOTH_PARTS: Barrier = Barrier::new(2);
+        fn waiting(parsed: &Parsed, params: &Params) -> Result<Answer, SolveError> {
+            BOTH_PARTS.wait();
+            count_lines(parsed, params)
+        }
+
+        let solution = Day {
+            parse: |input| Ok(parsed(input)),
+            part1: waiting,
+            part2: waiting,
+            ..get_day(1).unwrap()
+        };
+        let task = DayTask {
+            day: 1,
+            solution,
+            input: "a\nb".into(),
+            params: Params::new(&[]),
+            keys: [1, 2].map(|part| Key {
+                day: 1,
+                part,
+                input_hash: 0,
+                source_hash: 0,
+                shared_hash: 0,
+                params: String::new(),
+                variant: "default",
+            }),
+            cached: [None, None],
+        };
+
+        let outcomes = solve_days(&[task], None, None, Some(Duration::from_secs(5)), 2);
+        let DayOutcome::Solved { part1, part2, .. } = &outcomes[0] else {
+            panic!("day 1 was not solved");
+        };
+        assert_eq!(part1.answer, Ok(Answer::Int(2)));
+        assert_eq!(part2.answer, Ok(Answer::Int(2)));
+    }
+
+    #[test]
+    fn test_render_table() {
+        let reports = vec![
+            DayReport {
+                day: 1,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::from_millis(1),
+                    part1: PartResult {
+                        answer: Ok(Answer::Int(3)),
+                        elapsed: Duration::from_millis(1),
+                        cached: false,
+                    },
+                    part2: PartResult {
+                        answer: Err(PartError::Panic("boom".into())),
+                        elapsed: Duration::from_millis(2),
+                        cached: false,
+                    },
+                },
+                input_hash: Some(0),
+            },
+            DayReport {
+                day: 2,
+                outcome: DayOutcome::Solved {
+                    parse: Duration::ZERO,
+                    part1: PartResult {
+                        answer: Ok(Answer::from("abc")),
+                        elapsed: Duration::ZERO,
+                        cached: false,
+                    },
+                    part2: PartResult {
+                        answer: Err(PartError::Solve(SolveError::NotImplemented)),
+                        elapsed: Duration::ZERO,
+                        cached: false,
+                    },
+                },
+                input_hash: Some(0),
+            },
+            DayReport {
+                day: 12,
+                outcome: DayOutcome::NotImplemented,
+                input_hash: None,
+            },
+        ];
+        assert!(reports[0].failed());
+        assert!(!reports[1].failed());
+        let mut run = Run {
+            reports,
+            timing: Timing::Serial,
+            elapsed: Duration::from_millis(5),
+        };
+        let table = render_table(&run);
+        assert!(table.contains("12  | -      | not implemented"));
+        assert!(table.contains("| ERROR (panic) "));
+        let day2 = table.lines().find(|line| line.starts_with("2 ")).unwrap();
+        assert!(day2.contains("| abc ") && day2.contains("| not implemented "));
+        assert!(
+            table.ends_with("\nErrors:\nDay  1 part 2: panicked: boom\n\nTotal time: 4.00ms\n")
+        );
+
+        run.timing = Timing::Parallel { jobs: 4 };
+        let table = render_table(&run);
+        assert!(table.ends_with(
+            "Total time: 4.00ms\nWall-clock time: 5.00ms (4 jobs, part timings are contended)\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_example_table() {
+        let result = |answer: Result<Answer, PartError>, expected| ExampleResult {
+            day: 3,
+            example: 1,
+            part: 2,
+            result: PartResult {
+                answer,
+                elapsed: Duration::ZERO,
+                cached: false,
+            },
+            expected,
+        };
+        let results = [
+            result(Ok(Answer::Int(357)), Some("357")),
+            result(Ok(Answer::Int(98)), Some("89")),
+            result(Ok(Answer::Int(98)), None),
+            result(Err(PartError::Panic("boom".into())), Some("1")),
+        ];
+        let failed: Vec<bool> = results.iter().map(ExampleResult::failed).collect();
+        assert_eq!(failed, [false, true, false, true]);
+
+        let table = render_example_table(&results);
+        let statuses: Vec<&str> = table
+            .lines()
+            .skip(2)
+            .take(4)
+            .map(|line| line.rsplit("| ").next().unwrap())
+            .collect();
+        assert_eq!(statuses, ["PASS", "FAIL", "-", "ERROR"]);
+        assert!(table.ends_with("\nErrors:\nDay  3 example 1 part 2: panicked: boom\n"));
+    }
+}
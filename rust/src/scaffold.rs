@@ -0,0 +1,92 @@
+use crate::input::default_input_path;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn days_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/days")
+}
+
+/// Where the module of `day` lives, whether or not it exists yet.
+pub fn source_path(day: u8) -> PathBuf {
+    days_dir().join(format!("day{:02}.rs", day))
+}
+
+fn solution_name(day: u8) -> String {
+    format!("Day{:02}", day)
+}
+
+/// Creates `days/dayNN.rs` from the day00 template, with an empty `EXAMPLE`
+/// for the example of the puzzle text, and an empty input file. The build
+/// script registers the new module on the next build. Nothing is written if
+/// either file already exists.
+pub fn new_day(day: u8) -> Result<Vec<PathBuf>, String> {
+    create_day(day, source_path(day), default_input_path(day))
+}
+
+/// `new_day`, writing the module to `source` and the input to `input`.
+fn create_day(day: u8, source: PathBuf, input: PathBuf) -> Result<Vec<PathBuf>, String> {
+    let existing: Vec<String> = [&source, &input]
+        .into_iter()
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string())
+        .collect();
+    if !existing.is_empty() {
+        return Err(format!(
+            "Refusing to overwrite existing files: {}",
+            existing.join(", ")
+        ));
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))
+    };
+    let write = |path: &Path, content: &str| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+        }
+        fs::write(path, content).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    };
+
+    // A placeholder title, since every registered day must have one.
+    let template = read(&days_dir().join("day00.rs"))?
+        .replace("Day00", &solution_name(day))
+        .replace("title: \"\"", &format!("title: \"Day {:02}\"", day));
+
+    write(&source, &template)?;
+    write(&input, "")?;
+
+    Ok(vec![source, input])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_create_day() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        let source = dir.join("src/days/day10.rs");
+        let input = dir.join("inputs/day10.txt");
+
+        let created = create_day(10, source.clone(), input.clone()).unwrap();
+        assert_eq!(created, [source.clone(), input.clone()]);
+        let module = fs::read_to_string(&source).unwrap();
+        assert!(module.contains("pub struct Day10;"));
+        assert!(module.contains("title: \"Day 10\""));
+        assert!(module.contains("const EXAMPLE: &str = r#\"\"#;"));
+        assert!(module.contains("assert_examples::<Day10>(2);"));
+        assert!(!module.contains("Day00"));
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+
+        fs::write(&input, "1 2 3").unwrap();
+        let e = create_day(10, source.clone(), input.clone()).unwrap_err();
+        assert!(e.starts_with("Refusing to overwrite existing files"));
+        assert!(e.contains("day10.rs") && e.contains("day10.txt"));
+        assert_eq!(fs::read_to_string(&source).unwrap(), module);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1 2 3");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
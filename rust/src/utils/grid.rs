@@ -0,0 +1,268 @@
+use crate::parse::ParseError;
+use crate::utils::point::Point2;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A position in a grid, with `x` the column and `y` the row counted from
+/// the top left.
+pub type Pos = Point2<usize>;
+
+/// A rectangular grid of cells, stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid of `fill`.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is zero, since a grid without cells has no rows to iterate.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        assert!(width > 0, "A grid needs at least one column");
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses a character map with one row per line, turning each character
+    /// into a cell with `cell`. Characters it returns `None` for are reported
+    /// as errors expecting `expected`, as are rows of different lengths.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (i, line) in input.lines().enumerate() {
+            let mut columns = 0;
+            for (col, c) in line.char_indices() {
+                let found = &line[col..col + c.len_utf8()];
+                if width.is_some_and(|width| columns == width) {
+                    return Err(ParseError::new(
+                        i,
+                        line,
+                        found,
+                        format!("{} columns", columns),
+                    ));
+                }
+                cells.push(cell(c).ok_or_else(|| ParseError::new(i, line, found, expected))?);
+                columns += 1;
+            }
+            if *width.get_or_insert(columns) != columns {
+                return Err(ParseError::new(
+                    i,
+                    line,
+                    &line[line.len()..],
+                    format!("{} columns", width.unwrap()),
+                ));
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                cells,
+                width,
+                height,
+            }),
+            _ => Err(ParseError::empty_input("a row of the grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.y * self.width + pos.x])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.y * self.width + pos.x])
+    }
+
+    /// Replaces the cell at `pos`, returning whether it is in the grid.
+    pub fn set(&mut self, pos: Pos, value: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (Point2::new(i % self.width, i / self.width), cell))
+    }
+
+    /// The position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {} is outside the grid", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The positions left of, right of, above and below `pos` that are in
+    /// the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + use<T> {
+        let (width, height) = (self.width, self.height);
+        pos.neighbors4()
+            .filter(move |n| n.x < width && n.y < height)
+    }
+
+    /// The positions around `pos`, diagonals included, that are in the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + use<T> {
+        let (width, height) = (self.width, self.height);
+        pos.neighbors8()
+            .filter(move |n| n.x < width && n.y < height)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+/// Renders the grid as the character map it was parsed from, given a
+/// `Display` for the cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("ab#\nc#d\n", "a letter or '#'", |c| {
+            (c.is_ascii_lowercase() || c == '#').then_some(c)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point2::new(2, 1)], 'd');
+        assert_eq!(grid.to_string(), "ab#\nc#d\n");
+
+        let parse = |input| Grid::parse(input, "'.'", |c| (c == '.').then_some(c));
+        let e = parse("..\n.x").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "x"));
+        let e = parse("..\n...").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "2 columns"));
+        let e = parse("..\n.").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, ""));
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut grid = grid();
+        assert_eq!(grid.get(Point2::new(3, 0)), None);
+        assert_eq!(grid.get(Point2::new(0, 2)), None);
+        assert!(grid.set(Point2::new(0, 0), 'z'));
+        assert!(!grid.set(Point2::new(5, 5), 'z'));
+        assert_eq!(grid.find(&'z'), Some(Point2::new(0, 0)));
+        assert_eq!(grid.find(&'#'), Some(Point2::new(2, 0)));
+        assert_eq!(grid.find(&'q'), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one column")]
+    fn test_zero_width() {
+        Grid::new(0, 3, '.');
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbors4(Point2::new(0, 0)).collect::<Vec<_>>(),
+            [Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors4(Point2::new(1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8(Point2::new(2, 0)).collect::<Vec<_>>(),
+            [Point2::new(1, 0), Point2::new(1, 1), Point2::new(2, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point2::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['c', '#', 'd']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).collect::<String>(), "#d");
+        let columns: Vec<String> = grid.columns().map(|col| col.collect()).collect();
+        assert_eq!(columns, ["ac", "b#", "#d"]);
+    }
+}
@@ -1 +1,4 @@
+pub mod grid;
+pub mod point;
+pub mod rollback_union_find;
 pub mod union_find;
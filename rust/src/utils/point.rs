@@ -0,0 +1,309 @@
+use crate::parse::ParseError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// The integer types points can be made of.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// The distance between `self` and `other`, which cannot underflow even
+    /// for unsigned types.
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! coordinates {
+    ($($t:ty),*) => {$(
+        impl Coordinate for $t {
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+coordinates!(i32, i64, isize, u32, u64, usize);
+
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Component-wise arithmetic, and scaling by a number.
+macro_rules! point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $point { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $point { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $point { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+
+        impl<T: Coordinate> $point<T> {
+            /// The sum of the distances along each axis.
+            pub fn manhattan(&self, other: &Self) -> T {
+                [$(self.$field.abs_diff(other.$field)),+]
+                    .into_iter()
+                    .reduce(|a, b| a + b)
+                    .unwrap()
+            }
+
+            /// The largest of the distances along each axis, which is the
+            /// number of king moves between the points.
+            pub fn chebyshev(&self, other: &Self) -> T {
+                [$(self.$field.abs_diff(other.$field)),+]
+                    .into_iter()
+                    .max()
+                    .unwrap()
+            }
+
+            /// The square of the straight-line distance, which orders points
+            /// like the distance itself but stays exact.
+            pub fn squared_euclidean(&self, other: &Self) -> T {
+                [$(self.$field.abs_diff(other.$field)),+]
+                    .into_iter()
+                    .map(|d| d * d)
+                    .reduce(|a, b| a + b)
+                    .unwrap()
+            }
+        }
+    };
+}
+
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+/// Parses `N` comma-separated numbers from line `index` of the input,
+/// reporting errors at the offending number.
+fn parse_numbers<T: FromStr, const N: usize>(
+    index: usize,
+    line: &str,
+    expected: &str,
+) -> Result<[T; N], ParseError> {
+    let parts: Vec<&str> = line.trim().split(',').map(str::trim).collect();
+    let parts: [&str; N] = parts
+        .try_into()
+        .map_err(|_| ParseError::new(index, line, line.trim(), expected))?;
+    let mut numbers = Vec::with_capacity(N);
+    for s in parts {
+        numbers.push(
+            s.parse()
+                .map_err(|_| ParseError::new(index, line, s, "a number"))?,
+        );
+    }
+    Ok(numbers.try_into().ok().unwrap())
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: FromStr> Point2<T> {
+    /// Parses an `x,y` line of the puzzle input.
+    pub fn parse(index: usize, line: &str) -> Result<Self, ParseError> {
+        let [x, y] = parse_numbers(index, line, "two comma-separated numbers")?;
+        Ok(Point2 { x, y })
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    fn offset(&self, dx: Option<bool>, dy: Option<bool>) -> Option<Self> {
+        let step = |v: T, d: Option<bool>| match d {
+            None => Some(v),
+            Some(true) => v.checked_add(T::ONE),
+            Some(false) => v.checked_sub(T::ONE),
+        };
+        Some(Point2 {
+            x: step(self.x, dx)?,
+            y: step(self.y, dy)?,
+        })
+    }
+
+    /// The points left of, right of, above and below this one, skipping any
+    /// that the type cannot represent, such as negative unsigned ones.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Self> + use<T> {
+        let p = *self;
+        [
+            (Some(false), None),
+            (Some(true), None),
+            (None, Some(false)),
+            (None, Some(true)),
+        ]
+        .into_iter()
+        .filter_map(move |(dx, dy)| p.offset(dx, dy))
+    }
+
+    /// The points around this one, diagonals included, skipping any that the
+    /// type cannot represent.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Self> + use<T> {
+        let p = *self;
+        let steps = [Some(false), None, Some(true)];
+        steps
+            .into_iter()
+            .flat_map(move |dy| steps.into_iter().map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| dx.is_some() || dy.is_some())
+            .filter_map(move |(dx, dy)| p.offset(dx, dy))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: FromStr> Point3<T> {
+    /// Parses an `x,y,z` line of the puzzle input.
+    pub fn parse(index: usize, line: &str) -> Result<Self, ParseError> {
+        let [x, y, z] = parse_numbers(index, line, "three comma-separated numbers")?;
+        Ok(Point3 { x, y, z })
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    /// The six points sharing a face with this one, skipping any that the
+    /// type cannot represent.
+    pub fn neighbors6(&self) -> impl Iterator<Item = Self> + use<T> {
+        let p = *self;
+        let one = |v: T, up: bool| {
+            if up {
+                v.checked_add(T::ONE)
+            } else {
+                v.checked_sub(T::ONE)
+            }
+        };
+        [false, true]
+            .into_iter()
+            .flat_map(move |up| {
+                [
+                    one(p.x, up).map(|x| Point3 { x, ..p }),
+                    one(p.y, up).map(|y| Point3 { y, ..p }),
+                    one(p.z, up).map(|z| Point3 { z, ..p }),
+                ]
+            })
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(1, 5);
+        assert_eq!(a + b, Point2::new(4, 3));
+        assert_eq!(a - b, Point2::new(2, -7));
+        assert_eq!(a * 2, Point2::new(6, -4));
+        assert_eq!(-a, Point2::new(-3, 2));
+
+        let mut c = Point3::new(1_u64, 2, 3);
+        c += Point3::new(1, 1, 1);
+        c -= Point3::new(2, 0, 0);
+        assert_eq!(c, Point3::new(0, 3, 4));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point3::new(162_u64, 817, 812);
+        let b = Point3::new(425_u64, 690, 689);
+        assert_eq!(a.manhattan(&b), 263 + 127 + 123);
+        assert_eq!(b.chebyshev(&a), 263);
+        assert_eq!(a.squared_euclidean(&b), 263 * 263 + 127 * 127 + 123 * 123);
+        assert_eq!(Point2::new(-1, 4).manhattan(&Point2::new(2, 0)), 7);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Point2::parse(0, "7,1"), Ok(Point2::new(7_u64, 1)));
+        assert_eq!(Point3::parse(0, " 1, -2,3 "), Ok(Point3::new(1, -2, 3)));
+
+        let e = Point2::<u64>::parse(4, "7,x").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (5, 3, "x"));
+        assert_eq!(e.expected, "a number");
+        let e = Point3::<u64>::parse(0, "1,2").unwrap_err();
+        assert_eq!(e.expected, "three comma-separated numbers");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = Point2::new(0_u64, 0);
+        assert_eq!(
+            origin.neighbors4().collect::<Vec<_>>(),
+            [Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(origin.neighbors8().count(), 3);
+        assert_eq!(Point2::new(5_i32, 5).neighbors8().count(), 8);
+        assert!(
+            Point2::new(5_i32, 5)
+                .neighbors8()
+                .all(|n| n.chebyshev(&Point2::new(5, 5)) == 1)
+        );
+        assert_eq!(Point3::new(0_u32, 1, 1).neighbors6().count(), 5);
+        assert_eq!(Point3::new(1_i64, 1, 1).neighbors6().count(), 6);
+    }
+}
@@ -0,0 +1,229 @@
+/// A merge as recorded for undoing it: the root that was attached below
+/// another, and whether the rank of the new root grew.
+#[derive(Debug, Clone, Copy)]
+struct Merge {
+    child: usize,
+    root: usize,
+    rank_grew: bool,
+}
+
+/// A point in the merge history of a `RollbackUnionFind` to roll back to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snapshot(usize);
+
+/// Disjoint sets over `0..size` whose merges can be undone. Without path
+/// compression every merge changes a single parent, so undoing it is cheap,
+/// and union by rank keeps `find` logarithmic.
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+    /// One entry per call to `union`, `None` where it merged nothing, so
+    /// that the history can be rolled back a call at a time.
+    history: Vec<Option<Merge>>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(size: usize) -> Self {
+        RollbackUnionFind {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            size: vec![1; size],
+            components: size,
+            history: vec![],
+        }
+    }
+
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the components of `x` and `y`, returning whether they were
+    /// separate before. Every call is a step that `rollback` can undo.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut root = self.find(x);
+        let mut child = self.find(y);
+
+        if root == child {
+            self.history.push(None);
+            return false;
+        }
+
+        if self.rank[root] < self.rank[child] {
+            (root, child) = (child, root);
+        }
+        let rank_grew = self.rank[root] == self.rank[child];
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        if rank_grew {
+            self.rank[root] += 1;
+        }
+        self.components -= 1;
+        self.history.push(Some(Merge {
+            child,
+            root,
+            rank_grew,
+        }));
+        true
+    }
+
+    pub fn connected(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// The number of elements in the component of `x`.
+    pub fn size_of(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The sizes of all components, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.parent.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|x| self.size[x])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// The number of calls to `union` so far.
+    pub fn steps(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history.len())
+    }
+
+    /// Undoes every call to `union` made since `snapshot` was taken.
+    ///
+    /// # Panics
+    ///
+    /// If the snapshot was taken after steps that have already been rolled
+    /// back.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        assert!(
+            snapshot.0 <= self.history.len(),
+            "Cannot roll back to step {} after only {} steps",
+            snapshot.0,
+            self.history.len()
+        );
+        while self.history.len() > snapshot.0 {
+            if let Some(merge) = self.history.pop().unwrap() {
+                self.parent[merge.child] = merge.child;
+                self.size[merge.root] -= self.size[merge.child];
+                if merge.rank_grew {
+                    self.rank[merge.root] -= 1;
+                }
+                self.components += 1;
+            }
+        }
+    }
+}
+
+/// A fixed sequence of merges, such as the connections of day 8 in order of
+/// length, that can be queried after any number of them. Moving between
+/// steps applies or undoes only the merges in between.
+pub struct MergeSequence {
+    uf: RollbackUnionFind,
+    merges: Vec<(usize, usize)>,
+}
+
+impl MergeSequence {
+    pub fn new(size: usize, merges: Vec<(usize, usize)>) -> Self {
+        MergeSequence {
+            uf: RollbackUnionFind::new(size),
+            merges,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.merges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.merges.is_empty()
+    }
+
+    /// The components after the first `step` merges.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is beyond the end of the sequence.
+    pub fn at(&mut self, step: usize) -> &RollbackUnionFind {
+        assert!(
+            step <= self.merges.len(),
+            "Step {} is beyond the {} merges",
+            step,
+            self.merges.len()
+        );
+        if step < self.uf.steps() {
+            self.uf.rollback(Snapshot(step));
+        }
+        for &(x, y) in &self.merges[self.uf.steps()..step] {
+            self.uf.union(x, y);
+        }
+        &self.uf
+    }
+
+    /// The sizes of the components after the first `step` merges, largest
+    /// first.
+    pub fn component_sizes(&mut self, step: usize) -> Vec<usize> {
+        self.at(step).component_sizes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback() {
+        let mut uf = RollbackUnionFind::new(6);
+        assert!(uf.union(0, 1));
+        let snapshot = uf.snapshot();
+        assert!(uf.union(1, 2));
+        assert!(!uf.union(0, 2));
+        assert!(uf.union(3, 4));
+        assert_eq!(uf.steps(), 4);
+        assert_eq!(uf.component_sizes(), [3, 2, 1]);
+
+        uf.rollback(snapshot);
+        assert_eq!(uf.steps(), 1);
+        assert_eq!(uf.component_count(), 5);
+        assert_eq!(uf.size_of(0), 2);
+        assert!(!uf.connected(0, 2));
+        assert!(!uf.connected(3, 4));
+
+        uf.rollback(Snapshot(0));
+        assert_eq!(uf.component_sizes(), [1; 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot roll back")]
+    fn test_rollback_to_undone_step() {
+        let mut uf = RollbackUnionFind::new(3);
+        uf.union(0, 1);
+        let snapshot = uf.snapshot();
+        uf.rollback(Snapshot(0));
+        uf.rollback(snapshot);
+    }
+
+    #[test]
+    fn test_merge_sequence() {
+        let mut merges = MergeSequence::new(5, vec![(0, 1), (2, 3), (1, 3), (0, 2), (3, 4)]);
+        assert_eq!(merges.component_sizes(5), [5]);
+        assert_eq!(merges.component_sizes(2), [2, 2, 1]);
+        assert_eq!(merges.component_sizes(4), [4, 1]);
+        assert_eq!(merges.at(0).component_count(), 5);
+        assert_eq!(merges.at(3).steps(), 3);
+    }
+}
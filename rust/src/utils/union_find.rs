@@ -1,39 +1,79 @@
+/// Disjoint sets over `0..size`, merged by size with path halving. The
+/// size of each component is kept at its root, so that clustering puzzles
+/// can ask for it without bookkeeping of their own.
 pub struct UnionFind {
     parent: Vec<usize>,
-    rank: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
 }
 
 impl UnionFind {
     pub fn new(size: usize) -> Self {
         UnionFind {
             parent: (0..size).collect(),
-            rank: vec![0; size],
+            size: vec![1; size],
+            components: size,
         }
     }
 
-    pub fn find(&mut self, x: usize) -> usize {
-        if self.parent[x] != x {
-            self.parent[x] = self.find(self.parent[x]); // path compression
+    /// The root of the component of `x`. The search is iterative, so that it
+    /// cannot run out of stack however long the path to the root is.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            let grandparent = self.parent[self.parent[x]];
+            self.parent[x] = grandparent; // path halving
+            x = grandparent;
         }
-        self.parent[x]
+        x
     }
 
-    pub fn union(&mut self, x: usize, y: usize) {
-        let root_x = self.find(x);
-        let root_y = self.find(y);
+    /// Merges the components of `x` and `y`, returning whether they were
+    /// separate before.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut root_x = self.find(x);
+        let mut root_y = self.find(y);
 
         if root_x == root_y {
-            return;
+            return false;
         }
 
-        if self.rank[root_x] > self.rank[root_y] {
-            self.parent[root_y] = root_x;
-        } else if self.rank[root_x] < self.rank[root_y] {
-            self.parent[root_x] = root_y;
-        } else {
-            self.parent[root_y] = root_x;
-            self.rank[root_x] += 1;
+        if self.size[root_x] < self.size[root_y] {
+            (root_x, root_y) = (root_y, root_x);
         }
+        self.parent[root_y] = root_x;
+        self.size[root_x] += self.size[root_y];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// The number of elements in the component of `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The members of every component in ascending order, with the components
+    /// ordered by their smallest member.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_of_root = vec![usize::MAX; self.parent.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..self.parent.len() {
+            let root = self.find(x);
+            if group_of_root[root] == usize::MAX {
+                group_of_root[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[group_of_root[root]].push(x);
+        }
+        groups
     }
 }
 
@@ -44,8 +84,40 @@ mod tests {
     #[test]
     fn test_union() {
         let mut uf = UnionFind::new(10);
-        uf.union(1, 2);
-        uf.union(2, 3);
+        assert!(uf.union(1, 2));
+        assert!(uf.union(2, 3));
+        assert!(!uf.union(1, 3));
         assert_eq!(uf.find(1), uf.find(3));
     }
+
+    #[test]
+    fn test_deep_path() {
+        // Union by size never builds a path this deep, but `find` has to walk
+        // one without running out of stack all the same.
+        let n = 1_000_000;
+        let mut size = vec![1; n];
+        size[n - 1] = n;
+        let mut uf = UnionFind {
+            parent: (1..=n).map(|parent| parent.min(n - 1)).collect(),
+            size,
+            components: 1,
+        };
+        assert_eq!(uf.find(0), n - 1);
+        assert_eq!(uf.size_of(n / 2), n);
+        assert!(uf.connected(0, n - 2));
+    }
+
+    #[test]
+    fn test_sizes() {
+        let mut uf = UnionFind::new(5);
+        assert_eq!(uf.component_count(), 5);
+        uf.union(0, 1);
+        uf.union(3, 1);
+        uf.union(0, 3);
+        assert_eq!(uf.component_count(), 3);
+        assert_eq!(uf.size_of(3), 3);
+        assert_eq!(uf.size_of(2), 1);
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+    }
 }
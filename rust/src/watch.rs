@@ -0,0 +1,105 @@
+//! Re-running a day whenever its source or input changes, by polling their
+//! modification times. Polling needs no platform support, so it works the
+//! same on any filesystem, including those mounted into containers.
+
+use crate::input::{InputSource, input_path};
+use crate::scaffold::source_path;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The files a run of `day` depends on: its module, which also holds the
+/// examples, and its input if that is read from a file.
+pub fn watched_paths(day: u8, input: &InputSource) -> Vec<PathBuf> {
+    let mut paths = vec![source_path(day)];
+    match input {
+        InputSource::Profile(profile) => paths.push(input_path(profile, day)),
+        InputSource::Path(path) => paths.push(path.clone()),
+        InputSource::Stdin | InputSource::Inline(_) => {}
+    }
+    paths
+}
+
+/// The modification time and size of each file, or `None` for files that do
+/// not exist, so that creating or deleting a file also counts as a change.
+pub fn snapshot(paths: &[PathBuf]) -> Vec<Option<(SystemTime, u64)>> {
+    paths
+        .iter()
+        .map(|path| {
+            let metadata = fs::metadata(path).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+/// Runs this binary with `args` through cargo, so that changes to the source
+/// are compiled in first. The build uses the same profile as this binary.
+fn run(args: &[String]) -> Result<(), String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let mut command = Command::new(cargo);
+    command
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(manifest);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let status = command
+        .arg("--")
+        .args(args)
+        .status()
+        .map_err(|e| format!("Could not run cargo: {}", e))?;
+    if !status.success() {
+        println!("\nExited with {}", status);
+    }
+    Ok(())
+}
+
+/// Runs `args` every time one of the files of `day` changes, clearing the
+/// screen before each run. Only returns if cargo cannot be started.
+pub fn watch(day: u8, input: &InputSource, args: &[String]) -> Result<(), String> {
+    let paths = watched_paths(day, input);
+    let mut last = None;
+    loop {
+        let current = snapshot(&paths);
+        if last.as_ref() != Some(&current) {
+            last = Some(current);
+            print!("\x1b[2J\x1b[H");
+            let names: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+            println!("Watching {} (Ctrl-C to stop)\n", names.join(" and "));
+            run(args)?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = env::temp_dir().join(format!("aoc-watch-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let paths = vec![dir.join("day01.txt")];
+
+        let missing = snapshot(&paths);
+        assert_eq!(missing, [None]);
+
+        fs::write(&paths[0], "L68").unwrap();
+        let created = snapshot(&paths);
+        assert_ne!(created, missing);
+        assert_eq!(snapshot(&paths), created);
+
+        fs::write(&paths[0], "L68\nR48").unwrap();
+        assert_ne!(snapshot(&paths), created);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
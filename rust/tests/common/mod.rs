@@ -0,0 +1,32 @@
+//! Generated union-find inputs shared by the stress tests and the benchmark.
+
+use aoc2025::UnionFind;
+
+/// `count` random pairs of elements below `n`, drawn with SplitMix64 so that
+/// the inputs are reproducible without a dependency.
+pub fn random_edges(n: usize, count: usize) -> Vec<(usize, usize)> {
+    let mut state = 0x2025_u64;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31)) as usize % n
+    };
+    (0..count).map(|_| (next(), next())).collect()
+}
+
+/// Merges components of equal size pairwise until one is left. Union by size
+/// bounds the depth of a tree to about log2 n, and this builds trees of
+/// exactly that depth, as no `find` runs on anything but a root meanwhile.
+pub fn merge_pairwise(n: usize) -> UnionFind {
+    let mut uf = UnionFind::new(n);
+    let mut step = 1;
+    while step < n {
+        for i in (0..n - step).step_by(2 * step) {
+            uf.union(i, i + step);
+        }
+        step *= 2;
+    }
+    uf
+}
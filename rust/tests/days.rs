@@ -0,0 +1,89 @@
+use aoc2025::days::day09::{BoxBorderIter, rectangle_area};
+use aoc2025::utils::point::Point2;
+use aoc2025::{Answer, DAYS, Day, Example, SolveError, Status, get_day};
+
+#[test]
+fn test_registry() {
+    assert!(!DAYS.is_empty());
+    assert!(DAYS.windows(2).all(|w| w[0] < w[1]));
+    for &day in DAYS {
+        let solution = get_day(day).unwrap();
+        assert!(!solution.info.title.is_empty(), "day {} has no title", day);
+    }
+    assert!(get_day(0).is_none());
+    assert!(get_day(26).is_none());
+}
+
+fn solve(solution: Day, example: &Example) -> [Result<Answer, SolveError>; 2] {
+    let parsed = (solution.parse)(example.input).unwrap();
+    let params = solution.params(example.params.iter().copied());
+    [
+        (solution.part1)(&parsed, &params),
+        (solution.part2)(&parsed, &params),
+    ]
+}
+
+#[test]
+fn test_solved_days_answer_both_parts() {
+    for &day in DAYS {
+        let solution = get_day(day).unwrap();
+        let answers = solve(solution, &solution.examples[0]);
+        for (status, answer) in solution.info.status.iter().zip(answers) {
+            if *status == Status::Solved {
+                assert_ne!(answer, Err(SolveError::NotImplemented), "day {}", day);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_error() {
+    let solution = get_day(1).unwrap();
+    let Err(SolveError::Parse(e)) = (solution.parse)("L68\nX30") else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.column), (2, 1));
+}
+
+#[test]
+fn test_geometry_helpers() {
+    let a = Point2::new(2, 3);
+    let b = Point2::new(5, 6);
+    assert_eq!(rectangle_area(&a, &b), 16);
+    assert_eq!(BoxBorderIter::new(&a, &b).count(), 12);
+    assert_eq!(Point2::parse(0, "7,1"), Ok(Point2::new(7_u64, 1)));
+    assert_eq!(Point2::new(0_u64, 0).neighbors4().count(), 2);
+}
+
+#[test]
+fn test_registry_matches_files() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/days");
+    let mut on_disk: Vec<u8> = std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            name.strip_prefix("day")?.strip_suffix(".rs")?.parse().ok()
+        })
+        .filter(|&day| day > 0)
+        .collect();
+    on_disk.sort();
+    assert_eq!(on_disk, DAYS);
+}
+
+#[test]
+fn test_params() {
+    let solution = get_day(8).unwrap();
+    let example = &solution.examples[0];
+    let parsed = (solution.parse)(example.input).unwrap();
+    let answer = |params| (solution.part1)(&parsed, &params).map(|a| a.to_string());
+    assert_eq!(
+        answer(solution.params([("connections", 10)])),
+        Ok("40".into())
+    );
+    assert_eq!(
+        answer(solution.params([("connections", 10), ("circuits", 1)])),
+        Ok("5".into())
+    );
+    assert!(solution.declares("connections"));
+    assert!(!get_day(1).unwrap().declares("connections"));
+}
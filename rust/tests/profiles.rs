@@ -0,0 +1,51 @@
+use aoc2025::answers::CheckStatus;
+use aoc2025::input::{self, INPUT_DIR_VAR};
+use aoc2025::profiles::{render_profile_table, run_profiles};
+use std::env;
+use std::fs;
+
+const DAY01: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+/// The only test in this binary, because it points the inputs directory of
+/// the whole process at a scratch directory.
+#[test]
+fn test_profiles() {
+    let dir = env::temp_dir().join(format!("aoc-profiles-test-{}", std::process::id()));
+    fs::create_dir_all(dir.join("friend")).unwrap();
+    fs::create_dir_all(dir.join("stranger")).unwrap();
+    fs::write(dir.join("day01.txt"), DAY01).unwrap();
+    fs::write(
+        dir.join("answers.toml"),
+        "[day01]\npart1 = \"3\"\npart2 = \"6\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("friend/day01.txt"), DAY01).unwrap();
+    fs::write(
+        dir.join("friend/answers.toml"),
+        "[day01]\npart1 = \"3\"\npart2 = \"7\"\n",
+    )
+    .unwrap();
+    // SAFETY: no other test in this binary reads the environment.
+    unsafe { env::set_var(INPUT_DIR_VAR, &dir) };
+
+    let profiles = input::profiles();
+    assert_eq!(profiles, ["default", "friend", "stranger"]);
+
+    let runs = run_profiles(&profiles, &[1], &[], None, None, None, 1).unwrap();
+    assert!(!runs[0].failed());
+    assert!(runs[1].failed());
+    assert_eq!(
+        runs[1].checks[1].status,
+        CheckStatus::Fail {
+            expected: "7".into()
+        }
+    );
+    assert_eq!(runs[2].without_input, [1]);
+    assert!(!runs[2].failed());
+
+    let table = render_profile_table(&runs);
+    assert!(table.contains("2    | PASS    | FAIL   | no input"));
+    assert!(table.contains("Day  1 part 2 fails on friend (expected 7, got 6)"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
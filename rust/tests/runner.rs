@@ -0,0 +1,123 @@
+use aoc2025::cache::Cache;
+use aoc2025::{
+    Answer, DAYS, DayOutcome, InputSource, PartError, SolveError, cross_check, run_days,
+    run_examples,
+};
+
+#[test]
+fn test_run_days() {
+    let input = InputSource::Inline("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82".into());
+    let run = run_days(&[1], &input, &[], None, None, None, 1).unwrap();
+    assert_eq!(run.reports.len(), 1);
+    let DayOutcome::Solved { part1, part2, .. } = &run.reports[0].outcome else {
+        panic!("day 1 is solved");
+    };
+    assert_eq!(part1.answer.as_ref().ok(), Some(&Answer::Int(3)));
+    assert_eq!(part2.answer.as_ref().ok(), Some(&Answer::Int(6)));
+    assert!(run.reports[0].input_hash.is_some());
+}
+
+#[test]
+fn test_run_days_parse_error() {
+    let input = InputSource::Inline("L68\nX30".into());
+    let run = run_days(&[1], &input, &[], None, None, None, 1).unwrap();
+    assert!(run.reports[0].failed());
+    let DayOutcome::Solved { part1, .. } = &run.reports[0].outcome else {
+        panic!("day 1 is solved");
+    };
+    let Err(PartError::Solve(SolveError::Parse(e))) = &part1.answer else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(e.day, Some(1));
+}
+
+#[test]
+fn test_unregistered_day() {
+    let run = run_days(&[25], &InputSource::default(), &[], None, None, None, 1).unwrap();
+    assert!(matches!(run.reports[0].outcome, DayOutcome::NotImplemented));
+}
+
+#[test]
+fn test_run_examples() {
+    let results = run_examples(DAYS, None, None, 4);
+    assert!(!results.is_empty());
+    for result in &results {
+        assert!(
+            !result.failed(),
+            "day {}, example {}, part {}",
+            result.day,
+            result.example,
+            result.part
+        );
+    }
+}
+
+const DAY09: &str = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3";
+
+#[test]
+fn test_variant() {
+    let input = InputSource::Inline(DAY09.into());
+    for variant in ["raycast", "prefix_sum"] {
+        let run = run_days(&[9], &input, &[], Some(variant), None, None, 1).unwrap();
+        let DayOutcome::Solved { part2, .. } = &run.reports[0].outcome else {
+            panic!("day 9 is solved");
+        };
+        assert_eq!(
+            part2.answer.as_ref().ok(),
+            Some(&Answer::Int(24)),
+            "{}",
+            variant
+        );
+    }
+}
+
+#[test]
+fn test_cross_check() {
+    let input = InputSource::Inline(DAY09.into());
+    let checks = cross_check(&[9], &input, &[], None, 2).unwrap();
+    let variants: Vec<(u8, &str)> = checks.iter().map(|c| (c.part, c.variant)).collect();
+    assert_eq!(
+        variants,
+        [(1, "default"), (2, "raycast"), (2, "prefix_sum")]
+    );
+    assert!(checks.iter().all(|c| c.agrees && !c.failed()));
+
+    let input = InputSource::Inline("7,1".into());
+    let checks = cross_check(&[9], &input, &[], None, 1).unwrap();
+    assert!(checks.iter().all(|c| c.failed()));
+}
+
+#[test]
+fn test_cache() {
+    let dir = std::env::temp_dir().join(format!("aoc-runner-cache-{}", std::process::id()));
+    let input = InputSource::Inline(DAY09.into());
+    let cached = |cache: &Cache| {
+        let run = run_days(&[9], &input, &[], None, Some(cache), None, 1).unwrap();
+        let DayOutcome::Solved { part1, part2, .. } = &run.reports[0].outcome else {
+            panic!("day 9 is solved");
+        };
+        assert_eq!(part2.answer.as_ref().ok(), Some(&Answer::Int(24)));
+        [part1.cached, part2.cached]
+    };
+
+    assert_eq!(cached(&Cache::new(&dir)), [false, false]);
+    assert_eq!(cached(&Cache::new(&dir)), [true, true]);
+    assert_eq!(cached(&Cache::new(&dir).refreshing()), [false, false]);
+
+    let other_variant = run_days(
+        &[9],
+        &input,
+        &[],
+        Some("raycast"),
+        Some(&Cache::new(&dir)),
+        None,
+        1,
+    )
+    .unwrap();
+    let DayOutcome::Solved { part1, part2, .. } = &other_variant.reports[0].outcome else {
+        panic!("day 9 is solved");
+    };
+    assert!(part1.cached && !part2.cached);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
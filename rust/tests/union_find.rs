@@ -0,0 +1,66 @@
+mod common;
+
+use aoc2025::{MergeSequence, UnionFind};
+use common::{merge_pairwise, random_edges};
+
+#[test]
+fn test_components() {
+    let mut uf = UnionFind::new(6);
+    uf.union(0, 1);
+    uf.union(2, 3);
+    uf.union(1, 3);
+    assert_eq!(uf.find(0), uf.find(2));
+    assert_ne!(uf.find(0), uf.find(4));
+    assert_ne!(uf.find(4), uf.find(5));
+}
+
+#[test]
+fn test_groups() {
+    let mut uf = UnionFind::new(6);
+    assert!(uf.union(5, 0));
+    assert!(uf.union(3, 1));
+    assert!(!uf.union(0, 5));
+    assert_eq!(uf.groups(), [vec![0, 5], vec![1, 3], vec![2], vec![4]]);
+    assert_eq!(uf.component_count(), 4);
+    assert_eq!(uf.size_of(5), 2);
+}
+
+#[test]
+fn test_deepest_merges() {
+    let n = 1 << 21;
+    let mut uf = merge_pairwise(n);
+    assert_eq!(uf.component_count(), 1);
+    assert_eq!(uf.size_of(n - 1), n);
+    assert!((0..n).all(|x| uf.find(x) == 0));
+}
+
+#[test]
+fn test_random_clustering() {
+    let n = 1_000_000;
+    let edges = random_edges(n, n / 2);
+    let mut uf = UnionFind::new(n);
+    let merges = edges.iter().filter(|&&(x, y)| uf.union(x, y)).count();
+    assert_eq!(uf.component_count(), n - merges);
+    assert!(edges.iter().all(|&(x, y)| uf.connected(x, y)));
+
+    let groups = uf.groups();
+    assert_eq!(groups.len(), uf.component_count());
+    assert_eq!(groups.iter().map(Vec::len).sum::<usize>(), n);
+    assert!(groups.iter().all(|g| uf.size_of(g[0]) == g.len()));
+}
+
+#[test]
+fn test_merge_sequence_matches_union_find() {
+    let n = 1000;
+    let edges = random_edges(n, 2 * n);
+    let mut merges = MergeSequence::new(n, edges.clone());
+    for step in [1500, 10, 700, 2000, 0, 699] {
+        let mut uf = UnionFind::new(n);
+        for &(x, y) in &edges[..step] {
+            uf.union(x, y);
+        }
+        let mut expected: Vec<usize> = uf.groups().iter().map(Vec::len).collect();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(merges.component_sizes(step), expected);
+    }
+}